@@ -0,0 +1,216 @@
+use std::ops::Range;
+
+use crate::complex::Complex;
+use crate::equation::Equation;
+use crate::equation_element::{
+    AdditiveOperationType::Subtraction, EquationElement::*, OperationType::AdditiveOperation,
+    RelationType::*, Token,
+};
+use crate::equation_error::{
+    Diagnostic,
+    EquationError::{self, *},
+};
+use crate::equation_result::EquationResult::{self, *};
+use crate::inequality;
+use crate::interval::{self, Bound::*, Interval};
+use crate::scalar::Scalar;
+use crate::settings::Settings;
+
+// every absolute value doubles the number of sign cases which are solved
+const MAX_ABSOLUTE_VALUES: usize = 12;
+
+// the positions of the opening and the closing bar of an absolute value in the tokens
+struct AbsoluteValue {
+    opening: usize,
+    closing: usize,
+}
+
+// Each absolute value is replaced by its argument or by the negated argument. Every combination
+// of signs is solved as an ordinary equation and its solutions are only kept in the region where
+// the arguments have the assumed signs
+pub fn solve(tokens: Vec<Token>, settings: &Settings) -> Result<EquationResult, Diagnostic> {
+    let absolute_values = pair_bars(&tokens)?;
+    if absolute_values.len() > MAX_ABSOLUTE_VALUES {
+        return Err(TooManyAbsoluteValues {
+            max_amount: MAX_ABSOLUTE_VALUES,
+        }
+        .at(tokens[absolute_values[MAX_ABSOLUTE_VALUES].opening].span));
+    }
+    let relation = tokens
+        .iter()
+        .find_map(|token| match token.element {
+            Separator(relation) => Some(relation),
+            _ => None,
+        })
+        .unwrap_or(Equality);
+
+    let mut solution_set = Vec::new();
+    for case in 0..1 << absolute_values.len() {
+        let signs = (0..absolute_values.len())
+            .map(|index| case >> index & 1 == 0)
+            .collect::<Vec<bool>>();
+
+        let mut region = vec![Interval::real_line()];
+        for (absolute_value, positive) in absolute_values.iter().zip(&signs) {
+            let span = tokens[absolute_value.opening]
+                .span
+                .join(&tokens[absolute_value.closing].span);
+            let argument = expand(
+                &tokens,
+                absolute_value.opening + 1..absolute_value.closing,
+                &absolute_values,
+                &signs,
+            );
+            let (term, denominator) = Equation::expression(argument, settings)?;
+            let sign = match positive {
+                true => GreaterThanOrEqual,
+                false => LessThanOrEqual,
+            };
+            let argument_region =
+                solution_intervals(inequality::solve(&term, &denominator, sign, settings))
+                    .map_err(|error| error.at(span))?;
+            region = interval::intersection(&region, &argument_region, &settings.tolerance);
+        }
+        if region.is_empty() {
+            continue;
+        }
+
+        let case_tokens = expand(&tokens, 0..tokens.len(), &absolute_values, &signs);
+        let equation = Equation::generate(case_tokens, settings)?;
+        let case_solutions = solution_intervals(equation.solve(settings))?;
+        solution_set.extend(interval::intersection(
+            &case_solutions,
+            &region,
+            &settings.tolerance,
+        ));
+    }
+    Ok(EquationResult::from_intervals(
+        interval::union(solution_set, &settings.tolerance),
+        relation,
+        &settings.tolerance,
+    ))
+}
+
+fn pair_bars(tokens: &[Token]) -> Result<Vec<AbsoluteValue>, Diagnostic> {
+    let mut absolute_values: Vec<AbsoluteValue> = Vec::new();
+    let mut open = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        match token.element {
+            OpeningAbsoluteValue => {
+                open.push(absolute_values.len());
+                absolute_values.push(AbsoluteValue {
+                    opening: index,
+                    closing: index,
+                });
+            }
+            ClosingAbsoluteValue => {
+                let open_index = open.pop().ok_or(ParenthesisError.at(token.span))?;
+                absolute_values[open_index].closing = index;
+            }
+            Separator(_) if !open.is_empty() => {
+                let opening = absolute_values[open[open.len() - 1]].opening;
+                return Err(ParenthesisError.at(tokens[opening].span));
+            }
+            _ => {}
+        }
+    }
+    if let Some(open_index) = open.pop() {
+        return Err(ParenthesisError.at(tokens[absolute_values[open_index].opening].span));
+    }
+    Ok(absolute_values)
+}
+
+// the tokens in the range where each absolute value is replaced by its argument in parentheses,
+// which is negated if the sign of the absolute value is negative
+fn expand(
+    tokens: &[Token],
+    range: Range<usize>,
+    absolute_values: &[AbsoluteValue],
+    signs: &[bool],
+) -> Vec<Token> {
+    let mut expanded = Vec::new();
+    for index in range {
+        let Token { element, span } = &tokens[index];
+        let positive = absolute_values
+            .iter()
+            .zip(signs)
+            .find(|(absolute_value, _)| {
+                absolute_value.opening == index || absolute_value.closing == index
+            })
+            .is_none_or(|(_, positive)| *positive);
+        let elements = match (element, positive) {
+            (OpeningAbsoluteValue, true) => vec![OpeningParenthesis],
+            (OpeningAbsoluteValue, false) => vec![
+                OpeningParenthesis,
+                Operation(AdditiveOperation(Subtraction)),
+                OpeningParenthesis,
+            ],
+            (ClosingAbsoluteValue, true) => vec![ClosingParenthesis],
+            (ClosingAbsoluteValue, false) => vec![ClosingParenthesis, ClosingParenthesis],
+            (element, _) => vec![element.clone()],
+        };
+        expanded.extend(elements.into_iter().map(|element| Token {
+            element,
+            span: *span,
+        }));
+    }
+    expanded
+}
+
+// The solutions of a sign case as a set of intervals. Symbolic solutions such as x = a for |x| = a
+// cannot be compared with the region where the case applies, which would also need conditions
+// like a ≥ 0
+fn solution_intervals(
+    result: Result<EquationResult, EquationError>,
+) -> Result<Vec<Interval>, EquationError> {
+    let intervals = match result {
+        Ok(
+            Solutions {
+                solutions: values, ..
+            }
+            | BoundedSolutions {
+                solutions: values, ..
+            },
+        ) => values.into_iter().map(Interval::point).collect(),
+        Ok(ComplexSolutions(values)) => values
+            .into_iter()
+            .filter(Complex::is_real)
+            .map(|value| Interval::point(Scalar::Approximate(value.real)))
+            .collect(),
+        Ok(Unsolvable) | Err(ComplexNumbers) => Vec::new(),
+        Ok(InfiniteSolutions { mut exceptions }) => {
+            exceptions.sort_by(f64::total_cmp);
+            let mut intervals = Vec::new();
+            let mut lower = Unbounded;
+            for exception in exceptions {
+                intervals.push(Interval {
+                    lower,
+                    upper: Open(Scalar::Approximate(exception)),
+                });
+                lower = Open(Scalar::Approximate(exception));
+            }
+            intervals.push(Interval {
+                lower,
+                upper: Unbounded,
+            });
+            intervals
+        }
+        Ok(Intervals(intervals)) => intervals,
+        Ok(FilteredSolutions { solutions, .. }) => {
+            solutions.into_iter().map(Interval::point).collect()
+        }
+        Ok(ConditionalSolutions { .. }) => return Err(SymbolicParameters),
+        Ok(PeriodicSolutions { .. } | NumericSolutions { .. }) => {
+            return Err(TranscendentalEquation)
+        }
+        Err(error) => return Err(error),
+    };
+    let symbolic = intervals
+        .iter()
+        .flat_map(|interval| [&interval.lower, &interval.upper])
+        .any(|bound| matches!(bound, Open(value) | Closed(value) if value.is_symbolic()));
+    match symbolic {
+        true => Err(SymbolicParameters),
+        false => Ok(intervals),
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::locale::Locale;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Complex {
+    pub real: f64,
+    pub imaginary: f64,
+}
+
+impl Complex {
+    pub fn new(real: f64, imaginary: f64) -> Self {
+        Self { real, imaginary }
+    }
+
+    pub fn from_polar(radius: f64, angle: f64) -> Self {
+        Self::new(radius * angle.cos(), radius * angle.sin())
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.real.hypot(self.imaginary)
+    }
+
+    pub fn sqrt(&self) -> Self {
+        let norm = self.norm();
+        Self::new(
+            ((norm + self.real) / 2.0).sqrt(),
+            ((norm - self.real) / 2.0).sqrt().copysign(self.imaginary),
+        )
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.real, -self.imaginary)
+    }
+
+    pub fn is_real(&self) -> bool {
+        self.imaginary == 0.0
+    }
+
+    pub fn format(&self, locale: Locale) -> String {
+        let real = locale.format_number(self.real);
+        let imaginary = self.imaginary_part(locale);
+        match (self.real, self.imaginary) {
+            (_, 0.0) => real,
+            (0.0, _) if self.imaginary < 0.0 => format!("-{imaginary}"),
+            (0.0, _) => imaginary,
+            _ if self.imaginary < 0.0 => format!("{real} - {imaginary}"),
+            _ => format!("{real} + {imaginary}"),
+        }
+    }
+
+    pub fn format_with_conjugate(&self, locale: Locale) -> String {
+        match self.real {
+            0.0 => format!("±{}", self.imaginary_part(locale)),
+            real => format!(
+                "{} ± {}",
+                locale.format_number(real),
+                self.imaginary_part(locale)
+            ),
+        }
+    }
+
+    fn imaginary_part(&self, locale: Locale) -> String {
+        match self.imaginary.abs() {
+            1.0 => String::from("i"),
+            imaginary => format!("{}i", locale.format_number(imaginary)),
+        }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(real: f64) -> Self {
+        Self::new(real, 0.0)
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.format(Locale::Neutral))
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.real + other.real, self.imaginary + other.imaginary)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.real - other.real, self.imaginary - other.imaginary)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.real * other.real - self.imaginary * other.imaginary,
+            self.real * other.imaginary + self.imaginary * other.real,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let denominator = other.real * other.real + other.imaginary * other.imaginary;
+        Self::new(
+            (self.real * other.real + self.imaginary * other.imaginary) / denominator,
+            (self.imaginary * other.real - self.real * other.imaginary) / denominator,
+        )
+    }
+}
@@ -1,127 +1,372 @@
 use std::collections::VecDeque;
+use std::iter::Peekable;
+use std::vec::IntoIter;
 
 use crate::equation_element::{
-    EquationElement::{self, *},
+    AdditiveOperationType::*,
+    EquationElement::*,
     MultiplicativeOperationType::Multiplication,
+    OperationType::*,
+    RelationType::{self, *},
     SymbolType::*,
-    ValueType::Symbol,
+    Token,
+    ValueType::*,
 };
-use crate::equation_error::EquationError::{self, *};
+use crate::equation_error::{
+    Diagnostic,
+    EquationError::{self, *},
+};
+use crate::equation_result::EquationResult;
 use crate::equation_side::{EquationSide, EquationSideType::*};
+use crate::inequality;
 use crate::nested_term::NestedTerm;
+use crate::numeric;
+use crate::radical::{self, Radical};
+use crate::scalar::Scalar;
+use crate::settings::{RootFinding::ClosedForm, Settings, Tolerance};
+use crate::span::Span;
 use crate::term::Term;
+use crate::transcendental::{self, Transcendental};
 
 pub struct Equation {
     left_hand_side: Term,
     right_hand_side: Term,
+    relation: RelationType,
+    // the least common multiple of the denominators of both sides, which they were multiplied
+    // with to clear the fractions
+    denominator: Term,
+    // the roots of non-constant terms which appear as symbolic parameters in the sides
+    radicals: Vec<Radical>,
+    // the exponentials and logarithms which appear as symbolic parameters in the sides
+    transcendentals: Vec<Transcendental>,
 }
 
 impl Equation {
-    pub fn generate(tokens: Vec<EquationElement>) -> Result<Self, EquationError> {
+    pub fn generate(tokens: Vec<Token>, settings: &Settings) -> Result<Self, Diagnostic> {
         if tokens
             .iter()
-            .filter(|element| **element == Value(Symbol(Variable)))
-            .count()
-            == 0
+            .all(|token| token.element != Value(Symbol(Variable)))
         {
-            return Err(NoOccurrencesOfVariable);
+            return Err(NoOccurrencesOfVariable.into());
         }
+        Self::build(tokens, settings)
+    }
 
+    // the expression as a term together with the denominator it was multiplied with to clear
+    // its fractions, so its sign is the sign of the term times the sign of the denominator
+    pub fn expression(
+        mut tokens: Vec<Token>,
+        settings: &Settings,
+    ) -> Result<(Term, Term), Diagnostic> {
+        let span = tokens.last().map(|token| token.span).unwrap_or_default();
+        tokens.push(Token {
+            element: Separator(Equality),
+            span,
+        });
+        tokens.push(Token {
+            element: Value(Number(Scalar::zero())),
+            span,
+        });
+        let equation = Self::build(tokens, settings)?;
+        let denominator = equation.denominator.clone();
+        Ok((equation.set_zero(&settings.tolerance), denominator))
+    }
+
+    fn build(tokens: Vec<Token>, settings: &Settings) -> Result<Self, Diagnostic> {
         let mut nested_terms = VecDeque::new();
         let mut left_hand_side = EquationSide::new(LeftHandSide);
         let mut right_hand_side = EquationSide::new(RightHandSide);
         let mut current_equation_side = &mut left_hand_side;
-        let mut other_equation_side = &mut right_hand_side;
-        let mut current_equation_side_multiplier = Term::new_multiplier();
         let mut current_nested_term = NestedTerm::new();
-        let mut previous_element = Separator;
+        let mut previous_element = Separator(Equality);
+        let mut relation = Equality;
+        // the region of the factor which is merged into the current nested term next
+        let mut factor_span = None;
+        let mut radicals = Vec::new();
+        let mut transcendentals = Vec::new();
+
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(Token { element, span }) = tokens.next() {
+            if element != Operation(Exponentiation) {
+                current_nested_term
+                    .merge_factor(settings)
+                    .map_err(|error| error.at(factor_span))?;
+            }
 
-        for element in tokens {
             match element.clone() {
                 Value(value) => {
-                    current_nested_term.multiply_value(&value, &previous_element)?;
+                    current_nested_term
+                        .push_value(&value, &previous_element)
+                        .map_err(|error| error.at(span))?;
+                    factor_span = Some(span);
                 }
-                Operation(operation) => current_nested_term.set_operation(
-                    operation,
-                    &current_equation_side_multiplier,
-                    &previous_element,
-                )?,
-                Separator => {
+                Operation(Exponentiation) => {
+                    if let Value(_) | ClosingParenthesis = previous_element {
+                        let (exponent, exponent_span) = read_exponent(&mut tokens, span, settings)?;
+                        let power_span = factor_span.unwrap_or(span).join(&exponent_span);
+                        previous_element = match exponent.constant() {
+                            Some(exponent) => {
+                                current_nested_term
+                                    .raise_factor(&exponent, &mut radicals, settings)
+                                    .map_err(|error| error.at(power_span))?;
+                                Value(Number(exponent))
+                            }
+                            // a constant raised to an exponent containing the variable
+                            None => {
+                                current_nested_term
+                                    .exponentiate_factor(&exponent, &mut transcendentals, settings)
+                                    .map_err(|error| match error {
+                                        InvalidExponent => error.at(exponent_span),
+                                        error => error.at(power_span),
+                                    })?;
+                                Value(Symbol(Variable))
+                            }
+                        };
+                        factor_span = Some(power_span);
+                        continue;
+                    }
+                    return Err(InvalidOperation.at(span));
+                }
+                Operation(operation) => current_nested_term
+                    .set_operation(operation, &previous_element, settings)
+                    .map_err(|error| error.at(span))?,
+                Separator(separator_relation) => {
                     if let Value(_) | ClosingParenthesis = previous_element {
                         if let RightHandSide = current_equation_side.side {
-                            return Err(InvalidSeparatorAmount);
+                            return Err(InvalidSeparatorAmount.at(span));
                         }
-                        if !nested_terms.is_empty() {
-                            return Err(ParenthesisError);
+                        if let Some((_, opening_span, _)) = nested_terms.back() {
+                            return Err(ParenthesisError.at(*opening_span));
                         }
 
-                        current_nested_term.push_multiplier(&current_equation_side_multiplier);
+                        relation = separator_relation;
+                        current_nested_term.push_multiplier(settings);
                         current_equation_side.term = current_nested_term.term;
+                        current_equation_side.denominator = current_nested_term.denominator;
                         current_equation_side = &mut right_hand_side;
-                        other_equation_side = &mut left_hand_side;
-                        current_equation_side_multiplier = Term::new_multiplier();
                         current_nested_term = NestedTerm::new();
                     } else {
-                        return Err(InvalidSeparator);
+                        return Err(InvalidSeparator.at(span));
                     }
                 }
-                OpeningParenthesis => {
+                OpeningParenthesis | OpeningFunction(_) => {
                     if let Value(_) | ClosingParenthesis = previous_element {
                         current_nested_term.set_multiplicative_operation(Multiplication);
                     }
-                    nested_terms.push_back(current_nested_term);
+                    let function = match &element {
+                        OpeningFunction(function) => Some(function.clone()),
+                        _ => None,
+                    };
+                    nested_terms.push_back((current_nested_term, span, function));
                     current_nested_term = NestedTerm::new();
                 }
                 ClosingParenthesis => {
                     if let Value(_) | ClosingParenthesis = previous_element {
-                        current_nested_term.push_multiplier(&current_equation_side_multiplier);
-                        let mut nested_term = nested_terms.pop_back().ok_or(ParenthesisError)?;
-                        nested_term.merge_term(
-                            &current_nested_term.term,
-                            &mut current_equation_side_multiplier,
-                            other_equation_side,
-                        )?;
+                        current_nested_term.push_multiplier(settings);
+                        let (mut nested_term, opening_span, function) =
+                            nested_terms.pop_back().ok_or(ParenthesisError.at(span))?;
+                        let group_span = opening_span.join(&span);
+                        match function {
+                            // the arguments of functions have to be polynomials
+                            Some(_) if current_nested_term.denominator.constant().is_none() => {
+                                return Err(TranscendentalEquation.at(group_span))
+                            }
+                            Some(function) => nested_term.push_term(
+                                transcendental::apply(
+                                    &function,
+                                    &current_nested_term.term,
+                                    &mut transcendentals,
+                                    settings,
+                                )
+                                .map_err(|error| error.at(group_span))?,
+                                Term::new_multiplier(),
+                            ),
+                            None => nested_term.push_term(
+                                current_nested_term.term,
+                                current_nested_term.denominator,
+                            ),
+                        }
                         current_nested_term = nested_term;
+                        factor_span = Some(group_span);
                     } else {
-                        return Err(ParenthesisError);
+                        return Err(ParenthesisError.at(span));
                     }
                 }
+                OpeningAbsoluteValue | ClosingAbsoluteValue => unreachable!(),
             }
             previous_element = element;
         }
+        current_nested_term
+            .merge_factor(settings)
+            .map_err(|error| error.at(factor_span))?;
 
         if let LeftHandSide = current_equation_side.side {
-            return Err(InvalidSeparatorAmount);
+            return Err(InvalidSeparatorAmount.into());
         }
-        if !nested_terms.is_empty() {
-            return Err(ParenthesisError);
+        if let Some((_, opening_span, _)) = nested_terms.back() {
+            return Err(ParenthesisError.at(*opening_span));
         }
 
-        current_nested_term.push_multiplier(&current_equation_side_multiplier);
+        current_nested_term.push_multiplier(settings);
         current_equation_side.term = current_nested_term.term;
+        current_equation_side.denominator = current_nested_term.denominator;
 
-        if current_equation_side.multiplier == other_equation_side.multiplier {
-            current_equation_side.multiplier = Term::new_multiplier();
-            other_equation_side.multiplier = Term::new_multiplier();
-        }
-
-        current_equation_side.push_multiplier();
-        other_equation_side.push_multiplier();
+        // both sides are multiplied with the least common multiple of their denominators
+        let denominator = left_hand_side.clear_denominator(&mut right_hand_side, settings);
 
         Ok(Self {
             left_hand_side: left_hand_side.term,
             right_hand_side: right_hand_side.term,
+            relation,
+            denominator,
+            radicals,
+            transcendentals,
         })
     }
 
-    pub fn set_zero(self) -> Term {
+    pub fn is_equality(&self) -> bool {
+        self.relation == Equality
+    }
+
+    pub fn has_radicals(&self) -> bool {
+        !self.radicals.is_empty()
+    }
+
+    pub fn has_transcendentals(&self) -> bool {
+        !self.transcendentals.is_empty()
+    }
+
+    pub fn solve(self, settings: &Settings) -> Result<EquationResult, EquationError> {
+        let relation = self.relation;
+        let denominator = self.denominator.clone();
+        let radicals = self.radicals.clone();
+        let transcendentals = self.transcendentals.clone();
+        let term = self.set_zero(&settings.tolerance);
+        let result = match (relation, radicals.is_empty(), transcendentals.is_empty()) {
+            (_, false, false) => Err(TranscendentalEquation),
+            (_, false, true) => radical::solve(&term, &denominator, &radicals, relation, settings),
+            (_, true, false) => {
+                transcendental::solve(&term, &denominator, &transcendentals, relation, settings)
+            }
+            (Equality, true, true) => return term.zeroes(settings),
+            (_, true, true) => return inequality::solve(&term, &denominator, relation, settings),
+        };
+        // equations which cannot be reduced to polynomial ones are solved numerically unless
+        // only closed forms are requested
+        match result {
+            Err(TranscendentalEquation)
+                if relation == Equality && settings.root_finding != ClosedForm =>
+            {
+                Ok(numeric::solve(
+                    &term,
+                    &denominator,
+                    &radicals,
+                    &transcendentals,
+                    settings,
+                ))
+            }
+            result => result,
+        }
+    }
+
+    pub fn set_zero(self, tolerance: &Tolerance) -> Term {
         let mut term = self.left_hand_side.clone();
         for (exponent, coefficient) in self.right_hand_side.addends.iter() {
-            *term.addends.entry(*exponent).or_insert(0.0) -= coefficient;
+            *term.addends.entry(*exponent).or_insert_with(Scalar::zero) -= coefficient;
         }
 
-        term.exceptions_in_domain
-            .extend(&self.right_hand_side.exceptions_in_domain);
+        term.extend_exceptions(&self.right_hand_side, tolerance);
         term
     }
 }
+
+// the exponent and its region which starts after the exponentiation operator, which is a
+// polynomial in the variable for exponentials
+fn read_exponent(
+    tokens: &mut Peekable<IntoIter<Token>>,
+    operator_span: Span,
+    settings: &Settings,
+) -> Result<(Term, Span), Diagnostic> {
+    let mut negative = false;
+    let mut token = tokens.next();
+    if let Some(Token {
+        element: Operation(AdditiveOperation(operation)),
+        ..
+    }) = &token
+    {
+        negative = *operation == Subtraction;
+        token = tokens.next();
+    }
+
+    let (mut exponent, mut span) = match token {
+        Some(Token {
+            element: Value(value),
+            span,
+        }) => (Term::from_value(&value), span),
+        Some(Token {
+            element: OpeningParenthesis,
+            span,
+        }) => read_parenthesized_exponent(tokens, span, settings)?,
+        Some(Token { span, .. }) => return Err(InvalidExponent.at(span)),
+        None => return Err(InvalidExponent.at(operator_span)),
+    };
+
+    if let Some(Token {
+        span: next_operator_span,
+        ..
+    }) = tokens.next_if(|token| token.element == Operation(Exponentiation))
+    {
+        let (power, power_span) = read_exponent(tokens, next_operator_span, settings)?;
+        span = span.join(&power_span);
+        let constant = match (exponent.constant(), power.constant()) {
+            (Some(exponent), Some(power)) => exponent.pow(&power).ok_or(ComplexNumbers.at(span))?,
+            _ => return Err(InvalidExponent.at(span)),
+        };
+        exponent = Term::new_multiplier();
+        exponent.multiply_constant(&constant);
+    }
+    if negative {
+        exponent.multiply_constant(&Scalar::from(-1));
+    }
+    Ok((exponent, span))
+}
+
+// an exponent in parentheses has to be a constant like (1/3) or a polynomial like (2x + 1)
+fn read_parenthesized_exponent(
+    tokens: &mut Peekable<IntoIter<Token>>,
+    opening_span: Span,
+    settings: &Settings,
+) -> Result<(Term, Span), Diagnostic> {
+    let mut depth = 0;
+    let mut exponent_tokens = Vec::new();
+    let span = loop {
+        match tokens.next() {
+            Some(Token {
+                element: ClosingParenthesis,
+                span,
+            }) if depth == 0 => break opening_span.join(&span),
+            Some(token) => {
+                match token.element {
+                    OpeningParenthesis | OpeningFunction(_) => depth += 1,
+                    ClosingParenthesis => depth -= 1,
+                    _ => {}
+                }
+                exponent_tokens.push(token);
+            }
+            None => return Err(ParenthesisError.at(opening_span)),
+        }
+    };
+    if exponent_tokens.is_empty() {
+        return Err(InvalidExponent.at(span));
+    }
+
+    let (mut term, denominator) = Equation::expression(exponent_tokens, settings)?;
+    match denominator.constant() {
+        Some(denominator) if !denominator.is_symbolic() && !denominator.is_zero() => {
+            term.divide_constant(&denominator)
+                .map_err(|error| error.at(span))?;
+            Ok((term, span))
+        }
+        _ => Err(InvalidExponent.at(span)),
+    }
+}
@@ -1,28 +1,58 @@
+use crate::scalar::Scalar;
+use crate::span::Span;
+
+use RelationType::*;
+
+#[derive(Clone, PartialEq)]
+pub struct Token {
+    pub element: EquationElement,
+    pub span: Span,
+}
+
 #[derive(Clone, PartialEq)]
 pub enum EquationElement {
     Value(ValueType),
     Operation(OperationType),
-    Separator,
+    Separator(RelationType),
     OpeningParenthesis,
     ClosingParenthesis,
+    // the bars of |...| or abs(...), which are expanded into sign cases before an equation is built
+    OpeningAbsoluteValue,
+    ClosingAbsoluteValue,
+    // a function name together with the opening parenthesis of its argument, which is closed by a
+    // regular closing parenthesis
+    OpeningFunction(Function),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Function {
+    // the logarithm to a positive base other than one
+    Logarithm(Scalar),
+    Sine,
+    Cosine,
+    Tangent,
+    Arcsine,
+    Arccosine,
+    Arctangent,
 }
 
 #[derive(Clone, PartialEq)]
 pub enum ValueType {
-    Number(f64),
+    Number(Scalar),
     Symbol(SymbolType),
 }
 
 #[derive(Clone, PartialEq)]
 pub enum SymbolType {
     Variable,
-    Constant(f64),
+    Constant(Scalar),
 }
 
 #[derive(Clone, PartialEq)]
 pub enum OperationType {
     AdditiveOperation(AdditiveOperationType),
     MultiplicativeOperation(MultiplicativeOperationType),
+    Exponentiation,
 }
 
 #[derive(Clone, PartialEq)]
@@ -36,3 +66,35 @@ pub enum MultiplicativeOperationType {
     Multiplication,
     Division,
 }
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RelationType {
+    Equality,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    NotEqual,
+}
+
+impl RelationType {
+    // the relation written as "<=" or ">=" is tokenized as "<" or ">" followed by "="
+    pub fn or_equal(&self) -> Option<Self> {
+        match self {
+            LessThan => Some(LessThanOrEqual),
+            GreaterThan => Some(GreaterThanOrEqual),
+            _ => None,
+        }
+    }
+
+    pub fn holds(&self, value: f64) -> bool {
+        match self {
+            Equality => value == 0.0,
+            LessThan => value < 0.0,
+            LessThanOrEqual => value <= 0.0,
+            GreaterThan => value > 0.0,
+            GreaterThanOrEqual => value >= 0.0,
+            NotEqual => value != 0.0,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::span::Span;
+
 use EquationError::*;
 
 #[derive(PartialEq, Debug)]
@@ -6,20 +8,39 @@ pub enum EquationError {
     EmptyVariableName,
     NoOccurrencesOfVariable,
     InvalidElement(String),
+    InvalidNumber(String),
     MissingOperation,
     ParenthesisError,
     InvalidSeparator,
     InvalidSeparatorAmount,
     InvalidOperation,
+    InvalidExponent,
     DivisionByZero,
+    LogarithmOfZero,
     TooHighDegree { degree: i32, max_degree: i32 },
     ComplexNumbers,
+    NoConvergence,
+    NonlinearSystem,
+    InequalityInSystem,
+    SymbolicParameters,
+    RootOfSymbolicParameter,
+    TooManyAbsoluteValues { max_amount: usize },
+    TranscendentalEquation,
+    PeriodicInequality,
 }
 
 impl EquationError {
+    pub fn at(self, span: impl Into<Option<Span>>) -> Diagnostic {
+        Diagnostic {
+            error: self,
+            span: span.into(),
+        }
+    }
+
     pub fn log_message(&self) -> String {
         let message = match self {
             InvalidElement(element) => format!("Equation contains an invalid element: {element}"),
+            InvalidNumber(number) => format!("Equation contains a malformed number: {number}"),
             TooHighDegree { degree, max_degree } => format!(
                 "The equation has a degree of {degree} but equations with a degree greater than {max_degree} are not supported"
             ),
@@ -33,9 +54,69 @@ impl EquationError {
                 String::from("An equation must contain exactly one equals sign")
             }
             InvalidOperation => String::from("Equation contains invalid operation"),
+            InvalidExponent => String::from(
+                "Exponents must be constant and the variable can only be raised to integer powers",
+            ),
             DivisionByZero => String::from("Division by zero is undefined"),
+            LogarithmOfZero => String::from("The logarithm of zero is undefined"),
             ComplexNumbers => String::from("Complex numbers are not supported"),
+            NonlinearSystem => String::from("The system of equations is not linear in the unknowns"),
+            InequalityInSystem => {
+                String::from("Inequalities can only be solved for a single unknown")
+            }
+            SymbolicParameters => String::from(
+                "Symbolic parameters are only supported in equations which are linear in the variable",
+            ),
+            RootOfSymbolicParameter => {
+                String::from("Roots of symbolic parameters are not supported")
+            }
+            TooManyAbsoluteValues { max_amount } => format!(
+                "Equations with more than {max_amount} absolute values are not supported"
+            ),
+            TranscendentalEquation => String::from(
+                "Exponentials, logarithms and trigonometric functions can only be solved if the equation is polynomial in a single exponential or trigonometric function of a linear argument or linear in the logarithms",
+            ),
+            PeriodicInequality => {
+                String::from("Inequalities with periodic functions are not supported")
+            }
+            NoConvergence => {
+                String::from("Root finding did not converge to the requested precision")
+            }
         };
         format!("ERROR: {message}")
     }
 }
+
+// an error together with the region of the input that caused it
+#[derive(PartialEq, Debug)]
+pub struct Diagnostic {
+    pub error: EquationError,
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    // the log message followed by the input with the region of the error underlined
+    pub fn log_message(&self, equation: &str) -> String {
+        match self.span {
+            Some(span) => format!(
+                "{}\n{equation}\n{}",
+                self.error.log_message(),
+                span.underline()
+            ),
+            None => self.error.log_message(),
+        }
+    }
+
+    pub fn shift(self, bytes: usize, characters: usize) -> Self {
+        Self {
+            error: self.error,
+            span: self.span.map(|span| span.shift(bytes, characters)),
+        }
+    }
+}
+
+impl From<EquationError> for Diagnostic {
+    fn from(error: EquationError) -> Self {
+        Self { error, span: None }
+    }
+}
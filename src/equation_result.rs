@@ -1,38 +1,334 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use EquationResult::*;
 
+use crate::complex::Complex;
+use crate::equation_element::RelationType::{self, *};
+use crate::interval::{self, Bound::*, Interval};
+use crate::locale::Locale;
+use crate::scalar::Scalar;
+use crate::settings::Tolerance;
+use crate::trigonometry::{format_angle, format_period};
+
 #[derive(PartialEq, Debug)]
 pub enum EquationResult {
-    Solutions(Vec<f64>),
+    // distinct solutions with the multiplicity of each root
+    Solutions {
+        solutions: Vec<Scalar>,
+        multiplicities: Vec<usize>,
+    },
+    ComplexSolutions(Vec<Complex>),
     Unsolvable,
-    InfiniteSolutions { exceptions: Vec<f64> },
+    InfiniteSolutions {
+        exceptions: Vec<f64>,
+    },
+    Intervals(Vec<Interval>),
+    // solutions in terms of symbolic parameters which assume that the conditions are nonzero
+    ConditionalSolutions {
+        solutions: Vec<Scalar>,
+        conditions: Vec<Scalar>,
+    },
+    // solutions of equations with radicals or logarithms and the candidates which transforming
+    // them into polynomial equations introduced but which do not solve the original equation
+    FilteredSolutions {
+        solutions: Vec<Scalar>,
+        extraneous: Vec<Scalar>,
+    },
+    // the solutions in one period of a periodic function, which repeat with the period
+    PeriodicSolutions {
+        solutions: Vec<Scalar>,
+        period: Scalar,
+    },
+    // approximate roots of a polynomial with an estimate of the error of each root, which is zero
+    // for exact roots
+    BoundedSolutions {
+        solutions: Vec<Scalar>,
+        multiplicities: Vec<usize>,
+        errors: Vec<f64>,
+    },
+    // roots which a numeric search found in the range, each within its tolerance, which need
+    // not be all solutions
+    NumericSolutions {
+        solutions: Vec<Scalar>,
+        tolerances: Vec<f64>,
+        range: (f64, f64),
+    },
 }
 
-impl Display for EquationResult {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        let output = match self {
-            Solutions(values) if values.len() == 1 => values[0].to_string(),
-            Solutions(values) => format!(
-                "{{{}}}",
-                values
+impl EquationResult {
+    pub fn simple_solutions(solutions: Vec<Scalar>) -> Self {
+        Solutions {
+            multiplicities: vec![1; solutions.len()],
+            solutions,
+        }
+    }
+
+    // Equations have a set of solutions if they hold at single points only and infinitely many
+    // solutions if they hold everywhere except at single points, inequalities always have
+    // intervals
+    pub fn from_intervals(
+        intervals: Vec<Interval>,
+        relation: RelationType,
+        tolerance: &Tolerance,
+    ) -> Self {
+        if intervals.is_empty() {
+            return Unsolvable;
+        }
+        if relation != Equality {
+            return Intervals(intervals);
+        }
+
+        let points = intervals
+            .iter()
+            .map(|interval| interval.as_point(tolerance).cloned())
+            .collect::<Option<Vec<Scalar>>>();
+        if let Some(mut points) = points {
+            points.sort_by(|value, other| other.to_f64().total_cmp(&value.to_f64()));
+            return Self::simple_solutions(points);
+        }
+
+        let unbounded =
+            intervals[0].lower == Unbounded && intervals[intervals.len() - 1].upper == Unbounded;
+        let exceptions = intervals
+            .windows(2)
+            .map(|pair| match (&pair[0].upper, &pair[1].lower) {
+                (Open(upper), Open(lower)) if upper == lower => Some(upper.to_f64()),
+                _ => None,
+            })
+            .collect::<Option<Vec<f64>>>();
+        match (unbounded, exceptions) {
+            (true, Some(exceptions)) => InfiniteSolutions {
+                exceptions: exceptions.into_iter().rev().collect(),
+            },
+            _ => Intervals(intervals),
+        }
+    }
+
+    // The solution set of an equation which was transformed into a polynomial one whose zeroes
+    // are the candidates, so the candidates outside of the set are reported as extraneous
+    pub fn from_candidates(
+        solution_set: Vec<Interval>,
+        candidates: Vec<Scalar>,
+        relation: RelationType,
+        tolerance: &Tolerance,
+    ) -> Self {
+        let mut extraneous = candidates
+            .into_iter()
+            .filter(|candidate| {
+                interval::intersection(
+                    &solution_set,
+                    &[Interval::point(candidate.clone())],
+                    tolerance,
+                )
+                .is_empty()
+            })
+            .collect::<Vec<Scalar>>();
+        extraneous.sort_by(|value, other| other.to_f64().total_cmp(&value.to_f64()));
+        extraneous.dedup();
+        match (
+            Self::from_intervals(solution_set, relation, tolerance),
+            extraneous.is_empty(),
+        ) {
+            (Solutions { solutions, .. }, false) => FilteredSolutions {
+                solutions,
+                extraneous,
+            },
+            (Unsolvable, false) if relation == Equality => FilteredSolutions {
+                solutions: Vec::new(),
+                extraneous,
+            },
+            (result, _) => result,
+        }
+    }
+
+    // the members of periodic solution families between the bounds in ascending order
+    pub fn list_between(&self, lower: f64, upper: f64) -> Option<Vec<Scalar>> {
+        let (solutions, period) = match self {
+            PeriodicSolutions { solutions, period } => (solutions, period),
+            _ => return None,
+        };
+        let mut members = Vec::new();
+        for solution in solutions {
+            let first = ((lower - solution.to_f64()) / period.to_f64()).ceil() as i32;
+            let last = ((upper - solution.to_f64()) / period.to_f64()).floor() as i32;
+            for multiple in first..=last {
+                members.push(solution + &(period * &Scalar::from(multiple)));
+            }
+        }
+        members.sort_by(|value, other| value.to_f64().total_cmp(&other.to_f64()));
+        Some(members)
+    }
+}
+
+impl EquationResult {
+    pub fn format(&self, locale: Locale) -> String {
+        match self {
+            Solutions {
+                solutions: values,
+                multiplicities,
+            } => {
+                let output = format_values(
+                    values
+                        .iter()
+                        .zip(multiplicities)
+                        .map(|(value, multiplicity)| {
+                            format_multiplicity(value.format(locale), *multiplicity)
+                        })
+                        .collect(),
+                    locale,
+                );
+                match values.iter().any(Scalar::is_surd) {
+                    true => format!(
+                        "{output} ≈ {}",
+                        format_values(
+                            values
+                                .iter()
+                                .map(|value| locale.format_number(value.to_f64()))
+                                .collect(),
+                            locale
+                        )
+                    ),
+                    false => output,
+                }
+            }
+            ComplexSolutions(values) => {
+                let mut values = values.iter().peekable();
+                let mut formatted_values = Vec::new();
+                while let Some(value) = values.next() {
+                    let conjugate_pair =
+                        value.imaginary > 0.0 && values.next_if_eq(&&value.conjugate()).is_some();
+                    formatted_values.push(match conjugate_pair {
+                        true => value.format_with_conjugate(locale),
+                        false => value.format(locale),
+                    });
+                }
+                format_values(formatted_values, locale)
+            }
+            ConditionalSolutions {
+                solutions,
+                conditions,
+            } => format!(
+                "{} for {}",
+                format_values(
+                    solutions
+                        .iter()
+                        .map(|solution| solution.format(locale))
+                        .collect(),
+                    locale
+                ),
+                locale.join(
+                    &conditions
+                        .iter()
+                        .map(|condition| format!("{} ≠ 0", condition.format(locale)))
+                        .collect::<Vec<String>>()
+                )
+            ),
+            FilteredSolutions {
+                solutions,
+                extraneous,
+            } => format!(
+                "{} (removed extraneous {})",
+                Self::simple_solutions(solutions.clone()).format(locale),
+                format_values(
+                    extraneous
+                        .iter()
+                        .map(|value| value.format(locale))
+                        .collect(),
+                    locale
+                )
+            ),
+            PeriodicSolutions { solutions, period } => locale.join(
+                &solutions
                     .iter()
-                    .map(ToString::to_string)
-                    .collect::<Vec<String>>()
-                    .join(", ")
+                    .map(|solution| match solution.is_zero() {
+                        true => format_period(period, locale),
+                        false => format!(
+                            "{} + {}",
+                            format_angle(solution, locale),
+                            format_period(period, locale)
+                        ),
+                    })
+                    .collect::<Vec<String>>(),
+            ),
+            BoundedSolutions {
+                solutions,
+                multiplicities,
+                errors,
+            } => format_with_errors(solutions, multiplicities, errors, locale),
+            NumericSolutions {
+                solutions,
+                tolerances,
+                range: (lower, upper),
+            } => format!(
+                "{} (numeric search in [{}{}{}], not exhaustive)",
+                format_with_errors(solutions, &vec![1; solutions.len()], tolerances, locale),
+                locale.format_number(lower),
+                locale.list_separator(),
+                locale.format_number(upper)
             ),
             Unsolvable => String::from("{}"),
+            Intervals(intervals) => intervals
+                .iter()
+                .map(|interval| interval.format(locale))
+                .collect::<Vec<String>>()
+                .join(" ∪ "),
             InfiniteSolutions { exceptions } => match exceptions.is_empty() {
                 true => String::from("R"),
                 false => format!(
                     "R\\{{{}}}",
-                    exceptions
-                        .iter()
-                        .map(ToString::to_string)
-                        .collect::<Vec<String>>()
-                        .join(", ")
+                    locale.join(
+                        &exceptions
+                            .iter()
+                            .map(|exception| locale.format_number(exception))
+                            .collect::<Vec<String>>()
+                    )
                 ),
             },
-        };
-        write!(f, "{}", output)
+        }
+    }
+}
+
+impl Display for EquationResult {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.format(Locale::Neutral))
+    }
+}
+
+fn format_values(mut values: Vec<String>, locale: Locale) -> String {
+    match values.len() {
+        1 => values.remove(0),
+        _ => format!("{{{}}}", locale.join(&values)),
+    }
+}
+
+fn format_with_errors(
+    values: &[Scalar],
+    multiplicities: &[usize],
+    errors: &[f64],
+    locale: Locale,
+) -> String {
+    format_values(
+        values
+            .iter()
+            .zip(multiplicities)
+            .zip(errors)
+            .map(|((value, multiplicity), error)| {
+                let value = match *error == 0.0 {
+                    true => value.format(locale),
+                    false => format!("{} ± {error:.0e}", value.format(locale)),
+                };
+                format_multiplicity(value, *multiplicity)
+            })
+            .collect(),
+        locale,
+    )
+}
+
+fn format_multiplicity(value: String, multiplicity: usize) -> String {
+    match multiplicity {
+        1 => value,
+        2 => format!("{value} (double root)"),
+        3 => format!("{value} (triple root)"),
+        4 => format!("{value} (quadruple root)"),
+        _ => format!("{value} (root of multiplicity {multiplicity})"),
     }
 }
@@ -1,8 +1,9 @@
+use crate::settings::Settings;
 use crate::term::Term;
 
 pub struct EquationSide {
     pub term: Term,
-    pub multiplier: Term,
+    pub denominator: Term,
     pub side: EquationSideType,
 }
 
@@ -10,14 +11,27 @@ impl EquationSide {
     pub fn new(side: EquationSideType) -> Self {
         Self {
             term: Term::new(),
-            multiplier: Term::new_multiplier(),
+            denominator: Term::new_multiplier(),
             side,
         }
     }
 
-    pub fn push_multiplier(&mut self) {
-        self.term.multiply_term(&self.multiplier);
-        self.multiplier = Term::new_multiplier();
+    // multiplies both sides with the least common multiple of their denominators, which is
+    // returned
+    pub fn clear_denominator(&mut self, other: &mut EquationSide, settings: &Settings) -> Term {
+        let tolerance = &settings.tolerance;
+        let common_factor = self.denominator.gcd(&other.denominator, tolerance);
+        let (factor, _) = other
+            .denominator
+            .divide_polynomial(&common_factor, tolerance);
+        let (other_factor, _) = self
+            .denominator
+            .divide_polynomial(&common_factor, tolerance);
+        self.term.multiply_term(&factor);
+        other.term.multiply_term(&other_factor);
+        self.denominator.multiply_term(&factor);
+        other.denominator = self.denominator.clone();
+        self.denominator.clone()
     }
 }
 
@@ -1,78 +1,142 @@
 use ordered_float::OrderedFloat;
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 use crate::equation_error::EquationError;
+use crate::interval::{self, Interval};
+use crate::scalar::Scalar;
+use crate::settings::Tolerance;
+use crate::term::Term;
 
 use ExceptionsInDomain::*;
 
+// the exceptions are keyed by their value and keep the exact form they were computed in, such as
+// √2 for the divisor x^2 - 2
 #[derive(Clone, Debug)]
 pub enum ExceptionsInDomain {
-    Known(HashSet<OrderedFloat<f64>>),
-    Unknown { zero_is_valid: bool, degree: i32 },
+    Known(HashMap<OrderedFloat<f64>, Scalar>),
+    // known exceptions in a domain which is further restricted to intervals, such as x ≥ -3 for
+    // the square root of x + 3
+    Restricted {
+        exceptions: HashMap<OrderedFloat<f64>, Scalar>,
+        domain: Vec<Interval>,
+    },
+    // divisors whose zeroes could not be found, e.g. because their degree is too high. A value is
+    // tested against them by evaluating them there
+    Unresolved {
+        exceptions: HashMap<OrderedFloat<f64>, Scalar>,
+        divisors: Vec<Term>,
+        domain: Vec<Interval>,
+    },
 }
 
 impl ExceptionsInDomain {
-    pub fn extend(&mut self, other: &ExceptionsInDomain) {
-        match self {
-            Known(exceptions) => match other {
-                Known(other_exceptions) => exceptions.extend(other_exceptions.iter()),
-                Unknown {
-                    zero_is_valid,
-                    degree,
-                } => {
-                    let mut zero_is_valid = *zero_is_valid;
-                    if exceptions.contains(&OrderedFloat(0.0)) {
-                        zero_is_valid = true;
-                    }
-                    *self = Unknown {
-                        zero_is_valid,
-                        degree: *degree,
-                    };
-                }
+    pub fn extend(&mut self, other: &ExceptionsInDomain, tolerance: &Tolerance) {
+        if let (Known(exceptions), Known(other_exceptions)) = (&mut *self, other) {
+            exceptions.extend(
+                other_exceptions
+                    .iter()
+                    .map(|(key, value)| (*key, value.clone())),
+            );
+            return;
+        }
+
+        let mut exceptions = self.exceptions().clone();
+        exceptions.extend(
+            other
+                .exceptions()
+                .iter()
+                .map(|(key, value)| (*key, value.clone())),
+        );
+        let mut divisors = self.divisors().to_vec();
+        divisors.extend(other.divisors().iter().cloned());
+        let domain = interval::intersection(&self.domain(), &other.domain(), tolerance);
+        *self = match divisors.is_empty() {
+            true => Restricted { exceptions, domain },
+            false => Unresolved {
+                exceptions,
+                divisors,
+                domain,
             },
-            Unknown {
-                zero_is_valid,
-                degree,
-            } => {
-                if !other.zero_is_valid() {
-                    *zero_is_valid = false;
-                }
-                if let Unknown {
-                    degree: other_degree,
-                    ..
-                } = *other
-                {
-                    if other_degree > *degree {
-                        *degree = other_degree;
-                    }
-                }
-            }
+        };
+    }
+
+    pub fn unwrap_or<E: Fn(i32) -> EquationError>(
+        &self,
+        tolerance: &Tolerance,
+        error: E,
+    ) -> Result<HashMap<OrderedFloat<f64>, Scalar>, EquationError> {
+        match self {
+            Known(exceptions) | Restricted { exceptions, .. } => Ok(exceptions.to_owned()),
+            Unresolved { divisors, .. } => Err(error(
+                divisors
+                    .iter()
+                    .filter_map(|divisor| divisor.degree(tolerance))
+                    .max()
+                    .unwrap_or_default(),
+            )),
         }
     }
 
-    pub fn insert_zero(&mut self) {
+    // the exceptions which are known as values
+    pub fn exceptions(&self) -> &HashMap<OrderedFloat<f64>, Scalar> {
         match self {
-            Known(exceptions) => {
-                exceptions.insert(OrderedFloat(0.0));
+            Known(exceptions) | Restricted { exceptions, .. } | Unresolved { exceptions, .. } => {
+                exceptions
             }
-            Unknown { zero_is_valid, .. } => *zero_is_valid = false,
         }
     }
 
-    pub fn unwrap_or<E: Fn(i32) -> EquationError>(
-        &self,
-        error: E,
-    ) -> Result<HashSet<OrderedFloat<f64>>, EquationError> {
+    pub fn divisors(&self) -> &[Term] {
         match self {
-            Known(exceptions) => Ok(exceptions.to_owned()),
-            Unknown { degree, .. } => Err(error(*degree)),
+            Unresolved { divisors, .. } => divisors,
+            Known(_) | Restricted { .. } => &[],
         }
     }
 
-    pub fn zero_is_valid(&self) -> bool {
+    // the intervals the domain is restricted to apart from the single exceptions
+    pub fn domain(&self) -> Vec<Interval> {
         match self {
-            Known(exceptions) => !exceptions.contains(&OrderedFloat(0.0)),
-            Unknown { zero_is_valid, .. } => *zero_is_valid,
+            Restricted { domain, .. } | Unresolved { domain, .. } => domain.clone(),
+            Known(_) => vec![Interval::real_line()],
         }
     }
+
+    pub fn is_in_domain(&self, value: &Scalar, tolerance: &Tolerance) -> bool {
+        match self {
+            Restricted { domain, .. } | Unresolved { domain, .. } => {
+                !interval::intersection(domain, &[Interval::point(value.clone())], tolerance)
+                    .is_empty()
+            }
+            Known(_) => true,
+        }
+    }
+
+    // the value equals one of the known exceptions up to the tolerance or one of the unresolved
+    // divisors is negligible there
+    pub fn excludes(&self, value: f64, tolerance: &Tolerance) -> bool {
+        self.exceptions()
+            .keys()
+            .any(|exception| tolerance.approximately_equal(exception.into_inner(), value))
+            || self.divisors().iter().any(|divisor| {
+                let (divisor_value, magnitude) =
+                    divisor.evaluate_parameters(value, &HashMap::new());
+                !divisor_value.is_finite() || tolerance.is_negligible(divisor_value, magnitude)
+            })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Known(exceptions) => exceptions.is_empty(),
+            Restricted { .. } | Unresolved { .. } => false,
+        }
+    }
+
+    pub fn zero_is_valid(&self, tolerance: &Tolerance) -> bool {
+        !self.exceptions().contains_key(&OrderedFloat(0.0))
+            && self.is_in_domain(&Scalar::zero(), tolerance)
+            && self
+                .divisors()
+                .iter()
+                .all(|divisor| !divisor.coefficient(0).is_zero())
+    }
 }
@@ -0,0 +1,537 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::locale::Locale;
+use crate::scalar::Scalar;
+
+// the exponents of the parameters in a product of parameters
+type Monomial = BTreeMap<String, i32>;
+
+// a sum of numeric multiples of products of parameters
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polynomial {
+    terms: BTreeMap<Monomial, Scalar>,
+}
+
+// a quotient of polynomials in the symbolic parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    numerator: Polynomial,
+    denominator: Polynomial,
+}
+
+impl Polynomial {
+    fn constant(value: Scalar) -> Self {
+        let mut polynomial = Self::default();
+        polynomial.add_term(Monomial::new(), value);
+        polynomial
+    }
+
+    fn add_term(&mut self, monomial: Monomial, coefficient: Scalar) {
+        let sum = match self.terms.remove(&monomial) {
+            Some(other_coefficient) => &other_coefficient + &coefficient,
+            None => coefficient,
+        };
+        if !sum.is_zero() {
+            self.terms.insert(monomial, sum);
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    fn as_constant(&self) -> Option<Scalar> {
+        match self.terms.len() {
+            0 => Some(Scalar::zero()),
+            1 => self.terms.get(&Monomial::new()).cloned(),
+            _ => None,
+        }
+    }
+
+    fn scale(&self, factor: &Scalar) -> Self {
+        let mut product = Self::default();
+        for (monomial, coefficient) in self.terms.iter() {
+            product.add_term(monomial.clone(), coefficient * factor);
+        }
+        product
+    }
+
+    fn sum(&self, other: &Self) -> Self {
+        let mut sum = self.clone();
+        for (monomial, coefficient) in other.terms.iter() {
+            sum.add_term(monomial.clone(), coefficient.clone());
+        }
+        sum
+    }
+
+    fn product(&self, other: &Self) -> Self {
+        let mut product = Self::default();
+        for (monomial, coefficient) in self.terms.iter() {
+            for (other_monomial, other_coefficient) in other.terms.iter() {
+                let mut monomial = monomial.clone();
+                for (parameter, exponent) in other_monomial.iter() {
+                    *monomial.entry(parameter.clone()).or_insert(0) += exponent;
+                }
+                product.add_term(monomial, coefficient * other_coefficient);
+            }
+        }
+        product
+    }
+
+    // the parameters which divide every term with their lowest exponent
+    fn common_monomial(&self) -> Monomial {
+        let mut monomials = self.terms.keys();
+        let mut common = monomials.next().cloned().unwrap_or_default();
+        for monomial in monomials {
+            common = common
+                .into_iter()
+                .filter_map(|(parameter, exponent)| {
+                    monomial
+                        .get(&parameter)
+                        .map(|other_exponent| (parameter, exponent.min(*other_exponent)))
+                })
+                .collect();
+        }
+        common
+    }
+
+    fn divide_monomial(&self, divisor: &Monomial) -> Self {
+        let mut quotient = Self::default();
+        for (monomial, coefficient) in self.terms.iter() {
+            let mut monomial = monomial.clone();
+            for (parameter, exponent) in divisor.iter() {
+                if let Some(power) = monomial.get_mut(parameter) {
+                    *power -= exponent;
+                    if *power == 0 {
+                        monomial.remove(parameter);
+                    }
+                }
+            }
+            quotient.add_term(monomial, coefficient.clone());
+        }
+        quotient
+    }
+
+    // higher total degrees first and the constant last
+    fn ordered_terms(&self) -> Vec<(&Monomial, &Scalar)> {
+        let mut terms = self.terms.iter().collect::<Vec<(&Monomial, &Scalar)>>();
+        terms.sort_by_key(|(monomial, _)| std::cmp::Reverse(monomial.values().sum::<i32>()));
+        terms
+    }
+
+    fn leading_coefficient(&self) -> Scalar {
+        self.ordered_terms()
+            .first()
+            .map(|(_, coefficient)| (*coefficient).clone())
+            .unwrap_or_else(Scalar::zero)
+    }
+
+    // the same polynomial up to a constant factor with coprime integer coefficients where possible
+    // and a positive leading coefficient
+    fn normalized(&self) -> Self {
+        let factor = normalization_factor(self.terms.values())
+            .map(Scalar::Exact)
+            .unwrap_or_else(|| &Scalar::one() / &self.leading_coefficient().abs());
+        let normalized = self.scale(&factor);
+        match normalized.leading_coefficient().is_negative() {
+            true => normalized.scale(&Scalar::from(-1)),
+            false => normalized,
+        }
+    }
+
+    // the parameters and the remaining factor which all have to be nonzero for the polynomial to
+    // be nonzero
+    fn nonzero_factors(&self) -> Vec<Polynomial> {
+        let common = self.common_monomial();
+        let mut factors = common
+            .keys()
+            .map(|parameter| Self {
+                terms: BTreeMap::from([(Monomial::from([(parameter.clone(), 1)]), Scalar::one())]),
+            })
+            .collect::<Vec<Polynomial>>();
+        let remainder = self.divide_monomial(&common);
+        if remainder.as_constant().is_none() {
+            factors.push(remainder.normalized());
+        }
+        factors
+    }
+
+    fn term_count(&self) -> usize {
+        self.terms.len()
+    }
+
+    // the polynomials which are multiplied with the powers of the parameter
+    fn coefficients_of(&self, parameter: &str) -> BTreeMap<i32, Polynomial> {
+        let mut coefficients: BTreeMap<i32, Polynomial> = BTreeMap::new();
+        for (monomial, coefficient) in self.terms.iter() {
+            let mut monomial = monomial.clone();
+            let exponent = monomial.remove(parameter).unwrap_or_default();
+            coefficients
+                .entry(exponent)
+                .or_default()
+                .add_term(monomial, coefficient.clone());
+        }
+        coefficients
+    }
+
+    fn evaluate(&self, values: &HashMap<String, f64>) -> f64 {
+        self.terms
+            .iter()
+            .map(|(monomial, coefficient)| {
+                monomial
+                    .iter()
+                    .map(|(parameter, exponent)| {
+                        values
+                            .get(parameter)
+                            .copied()
+                            .unwrap_or(f64::NAN)
+                            .powi(*exponent)
+                    })
+                    .product::<f64>()
+                    * coefficient.to_f64()
+            })
+            .sum()
+    }
+
+    // the sum of the magnitudes of the terms, which bounds the rounding error of the evaluation
+    fn magnitude(&self, values: &HashMap<String, f64>) -> f64 {
+        self.terms
+            .iter()
+            .map(|(monomial, coefficient)| {
+                monomial
+                    .iter()
+                    .map(|(parameter, exponent)| {
+                        values
+                            .get(parameter)
+                            .copied()
+                            .unwrap_or(f64::NAN)
+                            .powi(*exponent)
+                    })
+                    .product::<f64>()
+                    .abs()
+                    * coefficient.to_f64().abs()
+            })
+            .sum()
+    }
+}
+
+// the factor which turns exact coefficients into coprime integers
+fn normalization_factor<'a>(coefficients: impl Iterator<Item = &'a Scalar>) -> Option<BigRational> {
+    let coefficients = coefficients
+        .map(|coefficient| match coefficient {
+            Scalar::Exact(value) => Some(value),
+            _ => None,
+        })
+        .collect::<Option<Vec<&BigRational>>>()?;
+    let denominator = coefficients
+        .iter()
+        .fold(BigInt::one(), |lcm, value| lcm.lcm(value.denom()));
+    let divisor = coefficients.iter().fold(BigInt::zero(), |gcd, value| {
+        gcd.gcd(&(*value * BigRational::from_integer(denominator.clone())).to_integer())
+    });
+    match divisor.is_zero() {
+        true => None,
+        false => Some(BigRational::new(denominator, divisor)),
+    }
+}
+
+impl Expression {
+    pub fn parameter(name: &str) -> Self {
+        Self {
+            numerator: Polynomial {
+                terms: BTreeMap::from([(Monomial::from([(name.to_string(), 1)]), Scalar::one())]),
+            },
+            denominator: Polynomial::constant(Scalar::one()),
+        }
+    }
+
+    pub fn pow(&self, exponent: i32) -> Self {
+        let mut power = Expression::from(&Scalar::one());
+        for _ in 0..exponent.abs() {
+            power = &power * self;
+        }
+        match exponent < 0 {
+            true => &Expression::from(&Scalar::one()) / &power,
+            false => power,
+        }
+    }
+
+    // the normalized polynomials which are assumed to be nonzero when the expression is assumed to
+    // be nonzero
+    pub fn nonzero_factors(&self) -> Vec<Expression> {
+        let mut factors = self.numerator.nonzero_factors();
+        factors.append(&mut self.denominator.nonzero_factors());
+        factors
+            .into_iter()
+            .map(|factor| Self {
+                numerator: factor,
+                denominator: Polynomial::constant(Scalar::one()),
+            })
+            .collect()
+    }
+
+    pub fn as_constant(&self) -> Option<Scalar> {
+        let numerator = self.numerator.as_constant()?;
+        let denominator = self.denominator.as_constant()?;
+        Some(&numerator / &denominator)
+    }
+
+    pub fn numerator(&self) -> Self {
+        Self {
+            numerator: self.numerator.clone(),
+            denominator: Polynomial::constant(Scalar::one()),
+        }
+    }
+
+    pub fn denominator(&self) -> Self {
+        Self {
+            numerator: self.denominator.clone(),
+            denominator: Polynomial::constant(Scalar::one()),
+        }
+    }
+
+    pub fn contains(&self, parameter: &str) -> bool {
+        self.numerator
+            .terms
+            .keys()
+            .chain(self.denominator.terms.keys())
+            .any(|monomial| monomial.contains_key(parameter))
+    }
+
+    // the expressions which are multiplied with the powers of a parameter which does not occur in
+    // the denominator
+    pub fn coefficients_of(&self, parameter: &str) -> BTreeMap<i32, Expression> {
+        self.numerator
+            .coefficients_of(parameter)
+            .into_iter()
+            .map(|(exponent, coefficient)| {
+                let coefficient = Self {
+                    numerator: coefficient,
+                    denominator: self.denominator.clone(),
+                };
+                (exponent, coefficient.simplify())
+            })
+            .collect()
+    }
+
+    pub fn evaluate(&self, values: &HashMap<String, f64>) -> f64 {
+        self.numerator.evaluate(values) / self.denominator.evaluate(values)
+    }
+
+    pub fn magnitude(&self, values: &HashMap<String, f64>) -> f64 {
+        self.numerator.magnitude(values) / self.denominator.evaluate(values).abs()
+    }
+
+    fn simplify(self) -> Self {
+        if self.numerator.is_zero() {
+            return Self::from(&Scalar::zero());
+        }
+
+        let numerator_common = self.numerator.common_monomial();
+        let common = self
+            .denominator
+            .common_monomial()
+            .into_iter()
+            .filter_map(|(parameter, exponent)| {
+                numerator_common
+                    .get(&parameter)
+                    .map(|other_exponent| (parameter, exponent.min(*other_exponent)))
+            })
+            .collect::<Monomial>();
+        let mut numerator = self.numerator.divide_monomial(&common);
+        let mut denominator = self.denominator.divide_monomial(&common);
+
+        let mut factor =
+            normalization_factor(numerator.terms.values().chain(denominator.terms.values()))
+                .map(Scalar::Exact)
+                .unwrap_or_else(Scalar::one);
+        if denominator.leading_coefficient().is_negative() {
+            factor = -&factor;
+        }
+        numerator = numerator.scale(&factor);
+        denominator = denominator.scale(&factor);
+
+        if let Some(constant) = denominator.as_constant() {
+            return Self {
+                numerator: numerator.scale(&(&Scalar::one() / &constant)),
+                denominator: Polynomial::constant(Scalar::one()),
+            };
+        }
+        let ratio = &numerator.leading_coefficient() / &denominator.leading_coefficient();
+        if numerator == denominator.scale(&ratio) {
+            return Self::from(&ratio);
+        }
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+}
+
+impl From<&Scalar> for Expression {
+    fn from(value: &Scalar) -> Self {
+        match value {
+            Scalar::Symbolic(expression) => expression.clone(),
+            value => Self {
+                numerator: Polynomial::constant(value.clone()),
+                denominator: Polynomial::constant(Scalar::one()),
+            },
+        }
+    }
+}
+
+impl From<Expression> for Scalar {
+    fn from(expression: Expression) -> Self {
+        let expression = expression.simplify();
+        match expression.as_constant() {
+            Some(constant) => constant,
+            None => Scalar::Symbolic(expression),
+        }
+    }
+}
+
+impl Add<&Expression> for &Expression {
+    type Output = Expression;
+
+    fn add(self, other: &Expression) -> Expression {
+        if self.denominator == other.denominator {
+            return Expression {
+                numerator: self.numerator.sum(&other.numerator),
+                denominator: self.denominator.clone(),
+            }
+            .simplify();
+        }
+        Expression {
+            numerator: self
+                .numerator
+                .product(&other.denominator)
+                .sum(&other.numerator.product(&self.denominator)),
+            denominator: self.denominator.product(&other.denominator),
+        }
+        .simplify()
+    }
+}
+
+impl Sub<&Expression> for &Expression {
+    type Output = Expression;
+
+    fn sub(self, other: &Expression) -> Expression {
+        self + &-other
+    }
+}
+
+impl Mul<&Expression> for &Expression {
+    type Output = Expression;
+
+    fn mul(self, other: &Expression) -> Expression {
+        Expression {
+            numerator: self.numerator.product(&other.numerator),
+            denominator: self.denominator.product(&other.denominator),
+        }
+        .simplify()
+    }
+}
+
+impl Div<&Expression> for &Expression {
+    type Output = Expression;
+
+    fn div(self, other: &Expression) -> Expression {
+        Expression {
+            numerator: self.numerator.product(&other.denominator),
+            denominator: self.denominator.product(&other.numerator),
+        }
+        .simplify()
+    }
+}
+
+impl Neg for &Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        Expression {
+            numerator: self.numerator.scale(&Scalar::from(-1)),
+            denominator: self.denominator.clone(),
+        }
+    }
+}
+
+impl Polynomial {
+    fn format(&self, locale: Locale) -> String {
+        // a leading minus sign is avoided by starting with the first positive term
+        let mut terms = self.ordered_terms();
+        if let Some(first_positive) = terms
+            .iter()
+            .position(|(_, coefficient)| !coefficient.is_negative())
+        {
+            let term = terms.remove(first_positive);
+            terms.insert(0, term);
+        }
+
+        let mut output = String::new();
+        for (monomial, coefficient) in terms {
+            let sign = match (output.is_empty(), coefficient.is_negative()) {
+                (true, true) => "-",
+                (true, false) => "",
+                (false, true) => " - ",
+                (false, false) => " + ",
+            };
+            let coefficient = coefficient.abs();
+            let parameters = monomial
+                .iter()
+                .map(|(parameter, exponent)| match exponent {
+                    1 => parameter.clone(),
+                    _ => format!("{parameter}^{exponent}"),
+                })
+                .collect::<Vec<String>>()
+                .join("·");
+            let term = match (parameters.is_empty(), coefficient == Scalar::one()) {
+                (true, _) => coefficient.format(locale),
+                (false, true) => parameters,
+                (false, false) if coefficient.is_exact() && coefficient.is_integer() => {
+                    format!("{}{parameters}", coefficient.format(locale))
+                }
+                (false, false) => format!("{}·{parameters}", coefficient.format(locale)),
+            };
+            output.push_str(&format!("{sign}{term}"));
+        }
+        if output.is_empty() {
+            output.push('0');
+        }
+        output
+    }
+}
+
+impl Expression {
+    pub fn format(&self, locale: Locale) -> String {
+        if self.denominator.as_constant() == Some(Scalar::one()) {
+            return self.numerator.format(locale);
+        }
+
+        let numerator = match self.numerator.term_count() {
+            1 => self.numerator.format(locale),
+            _ => format!("({})", self.numerator.format(locale)),
+        };
+        let single_parameter = self
+            .denominator
+            .terms
+            .iter()
+            .all(|(monomial, coefficient)| monomial.len() == 1 && *coefficient == Scalar::one());
+        let denominator = match self.denominator.term_count() == 1 && single_parameter {
+            true => self.denominator.format(locale),
+            false => format!("({})", self.denominator.format(locale)),
+        };
+        format!("{}/{}", numerator, denominator)
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.format(Locale::Neutral))
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use crate::equation_element::RelationType;
+use crate::equation_error::EquationError::{self, *};
+use crate::equation_result::EquationResult::{self, *};
+use crate::interval::{self, Bound::*, Interval};
+use crate::scalar::Scalar;
+use crate::settings::{Settings, Tolerance};
+use crate::term::Term;
+
+struct CriticalPoint {
+    value: Scalar,
+    exception: bool,
+}
+
+// Sign chart of the term divided by the denominator the equation was multiplied with. The sign
+// can only change at the zeroes of the term and at the exceptions in the domain, so a single test
+// value decides each interval between them
+pub fn solve(
+    term: &Term,
+    denominator: &Term,
+    relation: RelationType,
+    settings: &Settings,
+) -> Result<EquationResult, EquationError> {
+    if term.is_symbolic() || denominator.is_symbolic() || !term.symbolic_divisors.is_empty() {
+        return Err(SymbolicParameters);
+    }
+
+    let exceptions = term
+        .exceptions_in_domain
+        .unwrap_or(&settings.tolerance, |degree| TooHighDegree {
+            degree,
+            max_degree: settings.degree_limit(),
+        })?;
+
+    let mut exceptions = exceptions.into_values().collect::<Vec<Scalar>>();
+    exceptions.sort_by(|value, other| value.to_f64().total_cmp(&other.to_f64()));
+    let mut critical_points: Vec<CriticalPoint> = Vec::new();
+    let points = exceptions.into_iter().map(|value| (value, true)).chain(
+        term.real_zeroes(settings)?
+            .into_iter()
+            .map(|zero| (zero, false)),
+    );
+    // the exceptions come first, so a zero which equals one of them up to the tolerance is excluded
+    for (value, exception) in points {
+        if !critical_points.iter().any(|point| {
+            settings
+                .tolerance
+                .approximately_equal(point.value.to_f64(), value.to_f64())
+        }) {
+            critical_points.push(CriticalPoint { value, exception });
+        }
+    }
+    critical_points.sort_by(|point, other| point.value.to_f64().total_cmp(&other.value.to_f64()));
+
+    let satisfied =
+        |value: f64| relation.holds(term.evaluate(value) * denominator.evaluate(value).signum());
+    let test_values = test_values(
+        &critical_points
+            .iter()
+            .map(|point| point.value.to_f64())
+            .collect::<Vec<f64>>(),
+    );
+
+    let mut intervals = Vec::new();
+    let mut lower = satisfied(test_values[0]).then_some(Unbounded);
+    for (point, test_value) in critical_points.into_iter().zip(&test_values[1..]) {
+        let point_satisfied = !point.exception && relation.holds(0.0);
+        let next_satisfied = satisfied(*test_value);
+        match (lower.take(), point_satisfied, next_satisfied) {
+            (Some(bound), true, true) => lower = Some(bound),
+            (Some(bound), true, false) => intervals.push(Interval {
+                lower: bound,
+                upper: Closed(point.value),
+            }),
+            (Some(bound), false, next_satisfied) => {
+                intervals.push(Interval {
+                    lower: bound,
+                    upper: Open(point.value.clone()),
+                });
+                lower = next_satisfied.then_some(Open(point.value));
+            }
+            (None, true, true) => lower = Some(Closed(point.value)),
+            (None, true, false) => intervals.push(Interval {
+                lower: Closed(point.value.clone()),
+                upper: Closed(point.value),
+            }),
+            (None, false, true) => lower = Some(Open(point.value)),
+            (None, false, false) => {}
+        }
+    }
+    if let Some(bound) = lower {
+        intervals.push(Interval {
+            lower: bound,
+            upper: Unbounded,
+        });
+    }
+
+    Ok(match intervals.is_empty() {
+        true => Unsolvable,
+        false => Intervals(intervals),
+    })
+}
+
+// one value below, between and above the sorted critical points
+pub fn test_values(values: &[f64]) -> Vec<f64> {
+    let (first, last) = match (values.first(), values.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return vec![0.0],
+    };
+
+    let mut test_values = vec![first - first.abs().max(1.0)];
+    test_values.extend(values.windows(2).map(|pair| (pair[0] + pair[1]) / 2.0));
+    test_values.push(last + last.abs().max(1.0));
+    test_values
+}
+
+// The points and the open intervals between them where the relation holds. Away from the
+// points it can only hold on whole intervals if the equation is an identity
+pub fn sample(
+    critical_points: &[Scalar],
+    relation: RelationType,
+    identity: bool,
+    holds: impl Fn(f64) -> bool,
+    tolerance: &Tolerance,
+) -> Vec<Interval> {
+    let mut pieces = critical_points
+        .iter()
+        .filter(|point| holds(point.to_f64()))
+        .map(|point| Interval::point(point.clone()))
+        .collect::<Vec<Interval>>();
+    if identity || relation != RelationType::Equality {
+        let values = critical_points
+            .iter()
+            .map(Scalar::to_f64)
+            .collect::<Vec<f64>>();
+        for (position, test_value) in test_values(&values).into_iter().enumerate() {
+            if holds(test_value) {
+                pieces.push(Interval {
+                    lower: match position {
+                        0 => Unbounded,
+                        _ => Open(critical_points[position - 1].clone()),
+                    },
+                    upper: match critical_points.get(position) {
+                        Some(point) => Open(point.clone()),
+                        None => Unbounded,
+                    },
+                });
+            }
+        }
+    }
+    interval::union(pieces, tolerance)
+}
+
+// the bounds of the domain and the exceptions in it, where the sign of the term can change
+pub fn boundary_points(term: &Term, settings: &Settings) -> Result<Vec<Scalar>, EquationError> {
+    let mut points = Vec::new();
+    for interval in term.exceptions_in_domain.domain() {
+        for bound in [interval.lower, interval.upper] {
+            if let Open(value) | Closed(value) = bound {
+                points.push(value);
+            }
+        }
+    }
+    let exceptions = term
+        .exceptions_in_domain
+        .unwrap_or(&settings.tolerance, |degree| TooHighDegree {
+            degree,
+            max_degree: settings.degree_limit(),
+        })?;
+    points.extend(exceptions.into_values());
+    Ok(points)
+}
+
+// whether no divisor vanishes at the value with the symbolic parameters substituted
+pub fn divisors_defined_at(
+    term: &Term,
+    denominator: &Term,
+    value: f64,
+    parameters: &HashMap<String, f64>,
+    tolerance: &Tolerance,
+) -> bool {
+    term.symbolic_divisors
+        .iter()
+        .chain([denominator])
+        .all(|divisor| {
+            let (divisor_value, magnitude) = divisor.evaluate_parameters(value, parameters);
+            divisor_value.is_finite() && !tolerance.is_negligible(divisor_value, magnitude)
+        })
+}
+
+// whether the relation holds at the value with the symbolic parameters substituted, which fails
+// where a divisor vanishes
+pub fn holds_at(
+    term: &Term,
+    denominator: &Term,
+    relation: RelationType,
+    value: f64,
+    parameters: &HashMap<String, f64>,
+    tolerance: &Tolerance,
+) -> bool {
+    if !divisors_defined_at(term, denominator, value, parameters, tolerance) {
+        return false;
+    }
+
+    let (term_value, magnitude) = term.evaluate_parameters(value, parameters);
+    let sign = denominator
+        .evaluate_parameters(value, parameters)
+        .0
+        .signum();
+    match (
+        term_value.is_finite(),
+        tolerance.is_negligible(term_value, magnitude),
+    ) {
+        (false, _) => false,
+        (true, true) => relation.holds(0.0),
+        (true, false) => relation.holds(term_value * sign),
+    }
+}
@@ -0,0 +1,183 @@
+use std::cmp::Ordering::{self, *};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::locale::Locale;
+use crate::scalar::Scalar;
+use crate::settings::Tolerance;
+
+use Bound::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Interval {
+    pub lower: Bound,
+    pub upper: Bound,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Bound {
+    Unbounded,
+    Open(Scalar),
+    Closed(Scalar),
+}
+
+impl Interval {
+    pub fn real_line() -> Self {
+        Self {
+            lower: Unbounded,
+            upper: Unbounded,
+        }
+    }
+
+    pub fn point(value: Scalar) -> Self {
+        Self {
+            lower: Closed(value.clone()),
+            upper: Closed(value),
+        }
+    }
+
+    pub fn as_point(&self, tolerance: &Tolerance) -> Option<&Scalar> {
+        match (&self.lower, &self.upper) {
+            (Closed(lower), Closed(upper)) if compare(lower, upper, tolerance) == Equal => {
+                Some(lower)
+            }
+            _ => None,
+        }
+    }
+
+    fn intersect(&self, other: &Interval, tolerance: &Tolerance) -> Option<Interval> {
+        let lower = match compare_lower(&self.lower, &other.lower, tolerance) {
+            Less => other.lower.clone(),
+            Equal | Greater => self.lower.clone(),
+        };
+        let upper = match compare_upper(&self.upper, &other.upper, tolerance) {
+            Greater => other.upper.clone(),
+            Equal | Less => self.upper.clone(),
+        };
+        let nonempty = match (&lower, &upper) {
+            (Unbounded, _) | (_, Unbounded) => true,
+            (Closed(lower), Closed(upper)) => compare(lower, upper, tolerance) != Greater,
+            (Open(lower) | Closed(lower), Open(upper) | Closed(upper)) => {
+                compare(lower, upper, tolerance) == Less
+            }
+        };
+        nonempty.then_some(Interval { lower, upper })
+    }
+}
+
+// the intervals sorted and merged where they overlap or touch
+pub fn union(mut intervals: Vec<Interval>, tolerance: &Tolerance) -> Vec<Interval> {
+    intervals.sort_by(|interval, other| compare_lower(&interval.lower, &other.lower, tolerance));
+    let mut merged: Vec<Interval> = Vec::new();
+    for interval in intervals {
+        let touching = match merged.last().map(|last| (&last.upper, &interval.lower)) {
+            None => false,
+            Some((Unbounded, _) | (_, Unbounded)) => true,
+            Some((Open(upper), Open(lower))) => compare(lower, upper, tolerance) == Less,
+            Some((Open(upper) | Closed(upper), Open(lower) | Closed(lower))) => {
+                compare(lower, upper, tolerance) != Greater
+            }
+        };
+        match (touching, merged.last_mut()) {
+            (true, Some(last)) => {
+                if compare_upper(&last.upper, &interval.upper, tolerance) == Less {
+                    last.upper = interval.upper;
+                }
+            }
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+pub fn intersection(
+    intervals: &[Interval],
+    others: &[Interval],
+    tolerance: &Tolerance,
+) -> Vec<Interval> {
+    union(
+        intervals
+            .iter()
+            .flat_map(|interval| {
+                others
+                    .iter()
+                    .filter_map(|other| interval.intersect(other, tolerance))
+            })
+            .collect(),
+        tolerance,
+    )
+}
+
+// exact values are compared exactly and approximate ones up to the tolerance
+fn compare(value: &Scalar, other: &Scalar, tolerance: &Tolerance) -> Ordering {
+    if let (Scalar::Exact(value), Scalar::Exact(other)) = (value, other) {
+        return value.cmp(other);
+    }
+    let (value, other) = (value.to_f64(), other.to_f64());
+    match tolerance.approximately_equal(value, other) {
+        true => Equal,
+        false => value.total_cmp(&other),
+    }
+}
+
+// at the same value a closed lower bound comes before an open one
+fn compare_lower(bound: &Bound, other: &Bound, tolerance: &Tolerance) -> Ordering {
+    match (bound, other) {
+        (Unbounded, Unbounded) => Equal,
+        (Unbounded, _) => Less,
+        (_, Unbounded) => Greater,
+        (Open(value) | Closed(value), Open(other_value) | Closed(other_value)) => {
+            compare(value, other_value, tolerance).then(match (bound, other) {
+                (Closed(_), Open(_)) => Less,
+                (Open(_), Closed(_)) => Greater,
+                _ => Equal,
+            })
+        }
+    }
+}
+
+// at the same value an open upper bound comes before a closed one
+fn compare_upper(bound: &Bound, other: &Bound, tolerance: &Tolerance) -> Ordering {
+    match (bound, other) {
+        (Unbounded, Unbounded) => Equal,
+        (Unbounded, _) => Greater,
+        (_, Unbounded) => Less,
+        (Open(value) | Closed(value), Open(other_value) | Closed(other_value)) => {
+            compare(value, other_value, tolerance).then(match (bound, other) {
+                (Open(_), Closed(_)) => Less,
+                (Closed(_), Open(_)) => Greater,
+                _ => Equal,
+            })
+        }
+    }
+}
+
+impl Interval {
+    // the bounds are separated like listed values, e.g. [1,5; 2] with a decimal comma
+    pub fn format(&self, locale: Locale) -> String {
+        match (&self.lower, &self.upper) {
+            (Unbounded, Unbounded) => String::from("R"),
+            (Closed(lower), Closed(upper)) if lower == upper => {
+                format!("{{{}}}", lower.format(locale))
+            }
+            (lower, upper) => {
+                let lower = match lower {
+                    Unbounded => String::from("(-∞"),
+                    Open(bound) => format!("({}", bound.format(locale)),
+                    Closed(bound) => format!("[{}", bound.format(locale)),
+                };
+                let upper = match upper {
+                    Unbounded => String::from("∞)"),
+                    Open(bound) => format!("{})", bound.format(locale)),
+                    Closed(bound) => format!("{}]", bound.format(locale)),
+                };
+                format!("{lower}{}{upper}", locale.list_separator())
+            }
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.format(Locale::Neutral))
+    }
+}
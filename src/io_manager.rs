@@ -1,12 +1,21 @@
 use colored::*;
+use std::env;
+use std::f64::consts::PI;
 use std::io;
 use std::io::Write;
 
-use crate::equation_error::EquationError;
+use crate::equation_error::Diagnostic;
 use crate::equation_result::EquationResult;
+use crate::locale::Locale;
+use crate::settings::{AngleUnit, RootFinding::*, Settings};
+use crate::system_result::SystemResult;
 use crate::token_stream::EquationInput;
+use crate::trigonometry::format_angle;
+use crate::verification::Verification;
 
 pub fn read_input() -> EquationInput {
+    let settings = read_settings();
+
     println!("Enter equation");
 
     let mut equation = String::new();
@@ -24,13 +33,124 @@ pub fn read_input() -> EquationInput {
         .expect("Failed to read line");
     let variable_name = variable_name.trim().to_string();
 
-    EquationInput::new(equation, variable_name)
+    let mut input = EquationInput::new(equation, variable_name);
+    input.settings = settings;
+    input
+}
+
+fn read_settings() -> Settings {
+    let mut settings = Settings::default();
+    for argument in env::args().skip(1) {
+        match argument.split_once('=') {
+            Some(("--root-finding", "automatic")) => settings.root_finding = Automatic,
+            Some(("--root-finding", "closed-form")) => settings.root_finding = ClosedForm,
+            Some(("--root-finding", "numeric")) => settings.root_finding = Numeric,
+            Some(("--max-degree", value)) if value.parse::<i32>().is_ok() => {
+                settings.max_degree = value.parse().ok()
+            }
+            Some(("--precision", value)) if value.parse::<f64>().is_ok() => {
+                settings.precision = value.parse().unwrap()
+            }
+            Some(("--absolute-tolerance", value)) if value.parse::<f64>().is_ok() => {
+                settings.tolerance.absolute = value.parse().unwrap()
+            }
+            Some(("--relative-tolerance", value)) if value.parse::<f64>().is_ok() => {
+                settings.tolerance.relative = value.parse().unwrap()
+            }
+            Some(("--complex-solutions", value)) if value.parse::<bool>().is_ok() => {
+                settings.complex_solutions = value.parse().unwrap()
+            }
+            Some(("--error-bounds", value)) if value.parse::<bool>().is_ok() => {
+                settings.error_bounds = value.parse().unwrap()
+            }
+            Some(("--exact-arithmetic", value)) if value.parse::<bool>().is_ok() => {
+                settings.exact_arithmetic = value.parse().unwrap()
+            }
+            Some(("--locale", "neutral")) => settings.locale = Locale::Neutral,
+            Some(("--locale", "english")) => settings.locale = Locale::English,
+            Some(("--locale", "european")) => settings.locale = Locale::European,
+            Some(("--angle-unit", "radians")) => settings.angle_unit = AngleUnit::Radians,
+            Some(("--angle-unit", "degrees")) => settings.angle_unit = AngleUnit::Degrees,
+            Some(("--range", value)) if parse_range(value).is_some() => {
+                settings.solution_range = parse_range(value)
+            }
+            _ => println!(
+                "{}",
+                format!("WARNING: Ignoring argument {argument}").yellow()
+            ),
+        }
+    }
+    settings
+}
+
+// two bounds separated by a comma, which can be multiples of pi like 2pi
+fn parse_range(value: &str) -> Option<(f64, f64)> {
+    let parse_bound = |bound: &str| match bound.strip_suffix("pi") {
+        Some("") => Some(PI),
+        Some(factor) => factor.parse::<f64>().ok().map(|factor| factor * PI),
+        None => bound.parse::<f64>().ok(),
+    };
+    let (lower, upper) = value.split_once(',')?;
+    let (lower, upper) = (parse_bound(lower)?, parse_bound(upper)?);
+    (lower <= upper).then_some((lower, upper))
+}
+
+pub fn print_output(
+    result: Result<EquationResult, Diagnostic>,
+    verification: Option<Verification>,
+    equation: &str,
+    variable_name: String,
+    locale: Locale,
+    solution_range: Option<(f64, f64)>,
+) {
+    let members = solution_range.and_then(|(lower, upper)| {
+        let members = result.as_ref().ok()?.list_between(lower, upper)?;
+        Some(format!(
+            "{variable_name} ∈ [{}{}{}]: {{{}}}",
+            locale.format_number(lower),
+            locale.list_separator(),
+            locale.format_number(upper),
+            locale.join(
+                &members
+                    .iter()
+                    .map(|member| format_angle(member, locale))
+                    .collect::<Vec<String>>()
+            )
+        ))
+    });
+    let output = match result {
+        Ok(success_result @ EquationResult::Intervals(_)) => {
+            format!("{variable_name} ∈ {}", success_result.format(locale))
+        }
+        Ok(success_result) => format!("{variable_name} = {}", success_result.format(locale)),
+        Err(diagnostic) => format!("{}", diagnostic.log_message(equation).red()),
+    };
+    println!("{output}");
+    if let Some(members) = members {
+        println!("{members}");
+    }
+    if let Some(verification) = verification {
+        if let Some(residuals) = verification.format_residuals(&variable_name, locale) {
+            println!("{residuals}");
+        }
+        for rejection in verification.format_rejections(&variable_name, locale) {
+            println!("{}", rejection.yellow());
+        }
+        if let Some(unverified) = verification.format_unverified(&variable_name, locale) {
+            println!("{}", unverified.yellow());
+        }
+    }
 }
 
-pub fn print_output(result: Result<EquationResult, EquationError>, variable_name: String) {
+pub fn print_system_output(
+    result: Result<SystemResult, Diagnostic>,
+    equation: &str,
+    unknowns: &[String],
+    locale: Locale,
+) {
     let output = match result {
-        Ok(success_result) => format!("{variable_name} = {success_result}"),
-        Err(error) => format!("{}", error.log_message().red()),
+        Ok(success_result) => success_result.format(unknowns, locale),
+        Err(diagnostic) => format!("{}", diagnostic.log_message(equation).red()),
     };
     println!("{output}");
 }
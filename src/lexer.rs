@@ -0,0 +1,450 @@
+use num_bigint::BigInt;
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::equation_element::{
+    AdditiveOperationType::*,
+    EquationElement::{self, *},
+    MultiplicativeOperationType::*,
+    OperationType::*,
+    RelationType::*,
+    SymbolType::*,
+    ValueType::*,
+};
+use crate::equation_error::{Diagnostic, EquationError::*};
+use crate::locale::Locale;
+use crate::scalar::Scalar;
+use crate::span::Span;
+
+pub struct Lexeme {
+    pub kind: LexemeKind,
+    pub span: Span,
+}
+
+pub enum LexemeKind {
+    // the literal with '.' as the decimal separator
+    Number(String),
+    // an identifier or an unknown sequence of characters which is resolved by the caller
+    Word(String),
+    Element(EquationElement),
+    // a prefix root such as √ or ∛ with its index
+    Root(i32),
+    // '|' which opens or closes an absolute value depending on the preceding element
+    Bar,
+}
+
+pub struct Lexer<'a> {
+    source: &'a str,
+    characters: Peekable<CharIndices<'a>>,
+    character_index: usize,
+    locale: Locale,
+    // lexemes which were read together with a previous one, such as the exponent of x²
+    pending: VecDeque<Lexeme>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str, locale: Locale) -> Self {
+        Self {
+            source,
+            characters: source.char_indices().peekable(),
+            character_index: 0,
+            locale,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn position(&mut self) -> (usize, usize) {
+        let byte_index = match self.characters.peek() {
+            Some((byte_index, _)) => *byte_index,
+            None => self.source.len(),
+        };
+        (byte_index, self.character_index)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.characters.peek().map(|(_, character)| *character)
+    }
+
+    // the character n positions after the next one
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.characters
+            .clone()
+            .nth(n)
+            .map(|(_, character)| character)
+    }
+
+    // a grouping separator followed by exactly three digits
+    fn is_group(&self, separator: char) -> bool {
+        self.characters
+            .clone()
+            .next()
+            .map(|(_, character)| character)
+            == Some(separator)
+            && (1..=3).all(|n| self.peek_nth(n).is_some_and(|digit| digit.is_ascii_digit()))
+            && !self.peek_nth(4).is_some_and(|digit| digit.is_ascii_digit())
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (_, character) = self.characters.next()?;
+        self.character_index += 1;
+        Some(character)
+    }
+
+    fn bump_while(&mut self, text: &mut String, condition: impl Fn(char) -> bool) {
+        while let Some(character) = self.peek().filter(|character| condition(*character)) {
+            text.push(character);
+            self.bump();
+        }
+    }
+
+    // digits with an optional fraction and an optional exponent such as 6.02e23 or 1E-9. The 'e'
+    // only starts an exponent if digits follow, so 2e is still two times Euler's number
+    fn number(&mut self, start: (usize, usize)) -> Result<LexemeKind, Diagnostic> {
+        let mut text = String::new();
+        self.bump_while(&mut text, |character| character.is_ascii_digit());
+        // a mixed number such as 1½
+        if let Some((numerator, denominator)) = self.peek().and_then(vulgar_fraction) {
+            self.bump();
+            let integer = text.parse::<BigInt>().unwrap_or_default();
+            return Ok(LexemeKind::Number(format!(
+                "{}/{denominator}",
+                integer * denominator + numerator
+            )));
+        }
+        if let Some(separator) = self.locale.grouping_separator() {
+            while self.is_group(separator) {
+                self.bump();
+                self.bump_while(&mut text, |character| character.is_ascii_digit());
+            }
+        }
+        let locale = self.locale;
+        if self
+            .peek()
+            .is_some_and(|character| locale.is_decimal_mark(character))
+        {
+            self.bump();
+            text.push('.');
+            self.bump_while(&mut text, |character| character.is_ascii_digit());
+        }
+
+        let exponent = match (self.peek_nth(1), self.peek_nth(2)) {
+            (Some(digit), _) if digit.is_ascii_digit() => true,
+            (Some('+' | '-'), Some(digit)) => digit.is_ascii_digit(),
+            _ => false,
+        };
+        if exponent && matches!(self.peek(), Some('e' | 'E')) {
+            self.bump();
+            text.push('e');
+            if let Some(sign @ ('+' | '-')) = self.peek() {
+                self.bump();
+                text.push(sign);
+            }
+            self.bump_while(&mut text, |character| character.is_ascii_digit());
+        }
+
+        if let Some('.' | ',') = self.peek() {
+            self.bump_while(&mut text, |character| {
+                character.is_ascii_digit() || character == '.' || character == ','
+            });
+            return Err(InvalidNumber(text).at(Span::new(start, self.position())));
+        }
+        Ok(LexemeKind::Number(text))
+    }
+
+    // superscript powers such as x² or x⁻¹ are read as an exponentiation followed by the exponent
+    fn superscript(&mut self) -> LexemeKind {
+        let mut sign = None;
+        if let Some(character @ ('⁻' | '⁺')) = self.peek() {
+            let sign_start = self.position();
+            self.bump();
+            sign = Some(Lexeme {
+                kind: LexemeKind::Element(Operation(AdditiveOperation(match character {
+                    '⁻' => Subtraction,
+                    _ => Addition,
+                }))),
+                span: Span::new(sign_start, self.position()),
+            });
+        }
+
+        let digits_start = self.position();
+        let mut digits = String::new();
+        while let Some(digit) = self.peek().and_then(superscript_digit) {
+            digits.push(digit);
+            self.bump();
+        }
+        let span = Span::new(digits_start, self.position());
+        self.pending.extend(sign);
+        if !digits.is_empty() {
+            self.pending.push_back(Lexeme {
+                kind: LexemeKind::Number(digits),
+                span,
+            });
+        }
+        LexemeKind::Element(Operation(Exponentiation))
+    }
+
+    fn word(&mut self) -> LexemeKind {
+        let mut text = String::new();
+        self.bump_while(&mut text, |character| {
+            !character.is_whitespace()
+                && element(character).is_none()
+                && superscript_digit(character).is_none()
+                && !matches!(character, '⁻' | '⁺')
+                && vulgar_fraction(character).is_none()
+                && root_index(character).is_none()
+                && character != '|'
+        });
+        LexemeKind::Word(text)
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Lexeme, Diagnostic>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(lexeme) = self.pending.pop_front() {
+            return Some(Ok(lexeme));
+        }
+        while self.peek()?.is_whitespace() {
+            self.bump();
+        }
+
+        let start = self.position();
+        let character = self.peek()?;
+        let kind = match element(character) {
+            Some(element) => {
+                self.bump();
+                match (element, self.peek()) {
+                    (Separator(relation), Some('=')) if relation.or_equal().is_some() => {
+                        self.bump();
+                        LexemeKind::Element(Separator(relation.or_equal().unwrap()))
+                    }
+                    (element, _) => LexemeKind::Element(element),
+                }
+            }
+            None if superscript_digit(character).is_some() || matches!(character, '⁻' | '⁺') => {
+                self.superscript()
+            }
+            None if vulgar_fraction(character).is_some() => {
+                self.bump();
+                let (numerator, denominator) = vulgar_fraction(character).unwrap();
+                LexemeKind::Number(format!("{numerator}/{denominator}"))
+            }
+            None if character == '|' => {
+                self.bump();
+                LexemeKind::Bar
+            }
+            None if root_index(character).is_some() => {
+                self.bump();
+                LexemeKind::Root(root_index(character).unwrap())
+            }
+            None if character.is_ascii_digit()
+                || self.locale.is_decimal_mark(character)
+                    && self.peek_nth(1).is_some_and(|next| next.is_ascii_digit()) =>
+            {
+                match self.number(start) {
+                    Ok(kind) => kind,
+                    Err(diagnostic) => return Some(Err(diagnostic)),
+                }
+            }
+            None => self.word(),
+        };
+        Some(Ok(Lexeme {
+            kind,
+            span: Span::new(start, self.position()),
+        }))
+    }
+}
+
+// the characters which form an element on their own
+fn element(character: char) -> Option<EquationElement> {
+    Some(match character {
+        '+' => Operation(AdditiveOperation(Addition)),
+        '-' => Operation(AdditiveOperation(Subtraction)),
+        '−' => Operation(AdditiveOperation(Subtraction)),
+        '*' | '×' | '·' | '⋅' => Operation(MultiplicativeOperation(Multiplication)),
+        '/' | '÷' => Operation(MultiplicativeOperation(Division)),
+        '^' => Operation(Exponentiation),
+        '=' => Separator(Equality),
+        '<' => Separator(LessThan),
+        '>' => Separator(GreaterThan),
+        '≤' => Separator(LessThanOrEqual),
+        '≥' => Separator(GreaterThanOrEqual),
+        '≠' => Separator(NotEqual),
+        '(' => OpeningParenthesis,
+        ')' => ClosingParenthesis,
+        'π' => Value(Symbol(Constant(Scalar::Approximate(PI)))),
+        _ => return None,
+    })
+}
+
+fn superscript_digit(character: char) -> Option<char> {
+    Some(match character {
+        '⁰' => '0',
+        '¹' => '1',
+        '²' => '2',
+        '³' => '3',
+        '⁴' => '4',
+        '⁵' => '5',
+        '⁶' => '6',
+        '⁷' => '7',
+        '⁸' => '8',
+        '⁹' => '9',
+        _ => return None,
+    })
+}
+
+fn vulgar_fraction(character: char) -> Option<(i32, i32)> {
+    Some(match character {
+        '½' => (1, 2),
+        '⅓' => (1, 3),
+        '⅔' => (2, 3),
+        '¼' => (1, 4),
+        '¾' => (3, 4),
+        '⅕' => (1, 5),
+        '⅖' => (2, 5),
+        '⅗' => (3, 5),
+        '⅘' => (4, 5),
+        '⅙' => (1, 6),
+        '⅚' => (5, 6),
+        '⅐' => (1, 7),
+        '⅛' => (1, 8),
+        '⅜' => (3, 8),
+        '⅝' => (5, 8),
+        '⅞' => (7, 8),
+        '⅑' => (1, 9),
+        '⅒' => (1, 10),
+        _ => return None,
+    })
+}
+
+fn root_index(character: char) -> Option<i32> {
+    Some(match character {
+        '√' => 2,
+        '∛' => 3,
+        '∜' => 4,
+        _ => return None,
+    })
+}
+
+// the words which are applied to a following parenthesized argument
+pub fn is_function(word: &str) -> bool {
+    matches!(
+        word,
+        "abs"
+            | "exp"
+            | "ln"
+            | "log"
+            | "sqrt"
+            | "cbrt"
+            | "sin"
+            | "cos"
+            | "tan"
+            | "asin"
+            | "acos"
+            | "atan"
+            | "arcsin"
+            | "arccos"
+            | "arctan"
+    ) || word.starts_with("log_")
+}
+
+// A prefix root applies to the following number, word or parenthesized group including an
+// exponent of it, so √a^b is rewritten as ((a^b)^(1/2)). The inner parentheses keep the
+// exponentiation of the operand from being continued by the reciprocal of the index
+pub fn expand_roots(lexemes: Vec<Lexeme>) -> Result<Vec<Lexeme>, Diagnostic> {
+    let mut expanded = Vec::new();
+    // the index, the region and the parenthesis depth of the roots whose operand is not complete
+    let mut roots: Vec<(i32, Span, usize)> = Vec::new();
+    let mut awaiting_operand = None;
+    let mut depth = 0;
+    let mut lexemes = lexemes.into_iter().peekable();
+    while let Some(mut lexeme) = lexemes.next() {
+        // sqrt(...) and cbrt(...) are written like the prefix roots
+        if let LexemeKind::Word(word) = &lexeme.kind {
+            let opening_parenthesis = matches!(
+                lexemes.peek(),
+                Some(Lexeme {
+                    kind: LexemeKind::Element(OpeningParenthesis),
+                    ..
+                })
+            );
+            match (word.as_str(), opening_parenthesis) {
+                ("sqrt", true) => lexeme.kind = LexemeKind::Root(2),
+                ("cbrt", true) => lexeme.kind = LexemeKind::Root(3),
+                _ => {}
+            }
+        }
+        let completes_operand = match &lexeme.kind {
+            LexemeKind::Root(index) => {
+                roots.push((*index, lexeme.span, depth));
+                awaiting_operand = Some(lexeme.span);
+                for _ in 0..2 {
+                    expanded.push(Lexeme {
+                        kind: LexemeKind::Element(OpeningParenthesis),
+                        span: lexeme.span,
+                    });
+                }
+                continue;
+            }
+            LexemeKind::Element(OpeningParenthesis) => {
+                depth += 1;
+                false
+            }
+            LexemeKind::Element(ClosingParenthesis) => {
+                depth = depth.saturating_sub(1);
+                true
+            }
+            // the argument of a function belongs to the operand
+            LexemeKind::Word(word) if is_function(word) => !matches!(
+                lexemes.peek(),
+                Some(Lexeme {
+                    kind: LexemeKind::Element(OpeningParenthesis),
+                    ..
+                })
+            ),
+            LexemeKind::Number(_) | LexemeKind::Word(_) | LexemeKind::Element(Value(_)) => true,
+            LexemeKind::Element(_) | LexemeKind::Bar => {
+                if let Some(root_span) = awaiting_operand {
+                    return Err(InvalidOperation.at(root_span));
+                }
+                false
+            }
+        };
+        awaiting_operand = None;
+        expanded.push(lexeme);
+
+        let followed_by_exponent = matches!(
+            lexemes.peek(),
+            Some(Lexeme {
+                kind: LexemeKind::Element(Operation(Exponentiation)),
+                ..
+            })
+        );
+        if !completes_operand || followed_by_exponent {
+            continue;
+        }
+        while let Some((index, span, _)) = roots.last().filter(|root| root.2 == depth).copied() {
+            roots.pop();
+            expanded.push(Lexeme {
+                kind: LexemeKind::Element(ClosingParenthesis),
+                span,
+            });
+            expanded.push(Lexeme {
+                kind: LexemeKind::Element(Operation(Exponentiation)),
+                span,
+            });
+            expanded.push(Lexeme {
+                kind: LexemeKind::Number(format!("1/{index}")),
+                span,
+            });
+            expanded.push(Lexeme {
+                kind: LexemeKind::Element(ClosingParenthesis),
+                span,
+            });
+        }
+    }
+    Ok(expanded)
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::equation::Equation;
+use crate::equation_element::EquationElement::OpeningAbsoluteValue;
+use crate::equation_error::{Diagnostic, EquationError::*};
+use crate::expression::Expression;
+use crate::scalar::Scalar;
+use crate::settings::Settings;
+use crate::system_result::{
+    ParametrizedValue,
+    SystemResult::{self, *},
+};
+use crate::term::Term;
+use crate::token_stream::EquationInput;
+
+pub struct LinearSystem {
+    // one row per equation holding the coefficients of the unknowns and the right hand side
+    rows: Vec<Vec<Scalar>>,
+    unknown_count: usize,
+}
+
+impl LinearSystem {
+    pub fn generate(input: &EquationInput) -> Result<Self, Diagnostic> {
+        let unknowns = input.unknowns();
+        if unknowns.iter().any(String::is_empty) {
+            return Err(EmptyVariableName.into());
+        }
+        let equations = input.equations();
+        if equations.is_empty() {
+            return Err(EmptyEquation.into());
+        }
+
+        let rows = equations
+            .iter()
+            .map(|equation| {
+                let (bytes, characters) = input.offset_of(equation);
+                coefficient_row(input, equation, &unknowns)
+                    .map_err(|diagnostic| diagnostic.shift(bytes, characters))
+            })
+            .collect::<Result<Vec<Vec<Scalar>>, Diagnostic>>()?;
+        Ok(Self {
+            rows,
+            unknown_count: unknowns.len(),
+        })
+    }
+
+    // Gauss-Jordan elimination with partial pivoting
+    pub fn solve(mut self, settings: &Settings) -> SystemResult {
+        let magnitude = self
+            .rows
+            .iter()
+            .flatten()
+            .map(|value| value.to_f64().abs())
+            .fold(1.0, f64::max);
+        let negligible = |value: &Scalar| match value.is_exact() {
+            true => value.is_zero(),
+            false => settings.tolerance.is_negligible(value.to_f64(), magnitude),
+        };
+
+        let mut pivot_columns = Vec::new();
+        for column in 0..self.unknown_count {
+            let row = pivot_columns.len();
+            let pivot = match (row..self.rows.len()).max_by(|first, second| {
+                let first = self.rows[*first][column].to_f64().abs();
+                let second = self.rows[*second][column].to_f64().abs();
+                first.total_cmp(&second)
+            }) {
+                Some(pivot) if !negligible(&self.rows[pivot][column]) => pivot,
+                _ => continue,
+            };
+            self.rows.swap(row, pivot);
+
+            let pivot_value = self.rows[row][column].clone();
+            for value in self.rows[row].iter_mut() {
+                *value /= &pivot_value;
+            }
+            for other_row in 0..self.rows.len() {
+                let factor = self.rows[other_row][column].clone();
+                if other_row == row || factor.is_zero() {
+                    continue;
+                }
+                for other_column in 0..=self.unknown_count {
+                    let difference = &factor * &self.rows[row][other_column];
+                    self.rows[other_row][other_column] -= &difference;
+                }
+            }
+            pivot_columns.push(column);
+        }
+
+        if self.rows[pivot_columns.len()..]
+            .iter()
+            .any(|row| !negligible(&row[self.unknown_count]))
+        {
+            return Inconsistent;
+        }
+
+        let constants = (0..self.unknown_count)
+            .map(
+                |column| match pivot_columns.iter().position(|pivot| *pivot == column) {
+                    Some(row) => self.rows[row][self.unknown_count].clone(),
+                    None => Scalar::zero(),
+                },
+            )
+            .collect::<Vec<Scalar>>();
+        if pivot_columns.len() == self.unknown_count {
+            return Solution(constants);
+        }
+
+        let free_columns = (0..self.unknown_count)
+            .filter(|column| !pivot_columns.contains(column))
+            .collect::<Vec<usize>>();
+        SolutionFamily(
+            (0..self.unknown_count)
+                .map(
+                    |column| match pivot_columns.iter().position(|pivot| *pivot == column) {
+                        Some(row) => ParametrizedValue::Dependent {
+                            constant: constants[column].clone(),
+                            parameters: free_columns
+                                .iter()
+                                .filter(|free_column| !negligible(&self.rows[row][**free_column]))
+                                .map(|free_column| (*free_column, -&self.rows[row][*free_column]))
+                                .collect(),
+                        },
+                        None => ParametrizedValue::Free,
+                    },
+                )
+                .collect(),
+        )
+    }
+}
+
+fn coefficient_row(
+    input: &EquationInput,
+    equation: &str,
+    unknowns: &[String],
+) -> Result<Vec<Scalar>, Diagnostic> {
+    // the linearity is checked first, since substituting zero for an unknown in a divisor like
+    // x/y would report a division by zero instead
+    verify_linearity(input, equation, unknowns)?;
+
+    let mut row = Vec::new();
+    let mut constant = None;
+    for unknown in unknowns {
+        let substitutions = unknowns
+            .iter()
+            .filter(|other| *other != unknown)
+            .map(|other| (other.clone(), Scalar::zero()))
+            .collect();
+        match linear_term(input, equation, unknown, &substitutions) {
+            Ok(term) => {
+                row.push(term.coefficient(1));
+                constant = Some(term.coefficient(0));
+            }
+            Err(Diagnostic {
+                error: NoOccurrencesOfVariable,
+                ..
+            }) => row.push(Scalar::zero()),
+            Err(error) => return Err(error),
+        }
+    }
+    let constant = constant.ok_or(Diagnostic::from(NoOccurrencesOfVariable))?;
+
+    row.push(-&constant);
+    Ok(row)
+}
+
+// substituting the other unknowns with zero cannot reveal products of different unknowns, so the
+// equation is built again for every unknown with the others as symbolic parameters, where the
+// coefficient of a linear equation does not depend on any of them
+fn verify_linearity(
+    input: &EquationInput,
+    equation: &str,
+    unknowns: &[String],
+) -> Result<(), Diagnostic> {
+    for unknown in unknowns {
+        let substitutions = unknowns
+            .iter()
+            .filter(|other| *other != unknown)
+            .map(|other| (other.clone(), Scalar::from(Expression::parameter(other))))
+            .collect();
+        match linear_term(input, equation, unknown, &substitutions) {
+            Ok(term) if term.coefficient(1).is_symbolic() => return Err(NonlinearSystem.into()),
+            Ok(_)
+            | Err(Diagnostic {
+                error: NoOccurrencesOfVariable,
+                ..
+            }) => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+fn linear_term(
+    input: &EquationInput,
+    equation: &str,
+    unknown: &str,
+    substitutions: &HashMap<String, Scalar>,
+) -> Result<Term, Diagnostic> {
+    let tokens = input.system_token_stream(equation, unknown, substitutions)?;
+    if let Some(token) = tokens
+        .iter()
+        .find(|token| token.element == OpeningAbsoluteValue)
+    {
+        return Err(NonlinearSystem.at(token.span));
+    }
+    let equation = Equation::generate(tokens, &input.settings)?;
+    if !equation.is_equality() {
+        return Err(InequalityInSystem.into());
+    }
+    if equation.has_radicals() || equation.has_transcendentals() {
+        return Err(NonlinearSystem.into());
+    }
+    let term = equation.set_zero(&input.settings.tolerance);
+    match term.degree(&input.settings.tolerance).unwrap_or_default() <= 1
+        && term.lowest_exponent(&input.settings.tolerance) >= 0
+        && term.exceptions_in_domain.is_empty()
+    {
+        true => Ok(term),
+        false => Err(NonlinearSystem.into()),
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt::Display;
+use Locale::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Locale {
+    // either '.' or ',' is read as the decimal mark, the output uses '.' without grouping
+    Neutral,
+    // 1,000.5 and values listed as {1, 2}
+    English,
+    // 1.000,5 and values listed as {1; 2}
+    European,
+}
+
+impl Locale {
+    pub fn is_decimal_mark(&self, character: char) -> bool {
+        match self {
+            Neutral => matches!(character, '.' | ','),
+            English => character == '.',
+            European => character == ',',
+        }
+    }
+
+    pub fn decimal_mark(&self) -> char {
+        match self {
+            Neutral | English => '.',
+            European => ',',
+        }
+    }
+
+    pub fn grouping_separator(&self) -> Option<char> {
+        match self {
+            Neutral => None,
+            English => Some(','),
+            European => Some('.'),
+        }
+    }
+
+    pub fn list_separator(&self) -> &'static str {
+        match self {
+            Neutral | English => ", ",
+            European => "; ",
+        }
+    }
+
+    // Writes a number with the decimal mark and the grouping of the locale. Exponents of
+    // scientific notation such as 1e-15 and values like inf are kept as they are
+    pub fn format_number(&self, number: impl Display) -> String {
+        let number = number.to_string();
+        let (sign, unsigned) = match number.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", number.as_str()),
+        };
+        let (mantissa, exponent) = match unsigned.split_once('e') {
+            Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+            None => (unsigned, None),
+        };
+        let (integer, fraction) = match mantissa.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (mantissa, None),
+        };
+        if !integer.chars().all(|digit| digit.is_ascii_digit()) {
+            return number;
+        }
+
+        // like 2024, numbers with four digits are written without grouping
+        let grouping_separator = self.grouping_separator().filter(|_| integer.len() > 4);
+        let mut formatted = String::from(sign);
+        for (index, digit) in integer.chars().enumerate() {
+            if let Some(separator) = grouping_separator {
+                if index > 0 && (integer.len() - index) % 3 == 0 {
+                    formatted.push(separator);
+                }
+            }
+            formatted.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            formatted.push(self.decimal_mark());
+            formatted.push_str(fraction);
+        }
+        if let Some(exponent) = exponent {
+            formatted.push('e');
+            formatted.push_str(exponent);
+        }
+        formatted
+    }
+
+    pub fn join(&self, values: &[String]) -> String {
+        values.join(self.list_separator())
+    }
+}
@@ -1,19 +1,60 @@
+mod absolute_value;
+mod complex;
 mod equation;
 mod equation_element;
 mod equation_error;
 mod equation_result;
 mod equation_side;
 mod exceptions_in_domain;
+mod expression;
+mod inequality;
+mod interval;
 mod io_manager;
+mod lexer;
+mod linear_system;
+mod locale;
 mod nested_term;
+mod numeric;
+mod radical;
+mod roots;
+mod scalar;
+mod settings;
 mod solve_equation;
+mod solve_system;
+mod span;
+mod sturm;
+mod surd;
+mod system_result;
 mod term;
 #[cfg(test)]
 mod tests;
 mod token_stream;
+mod transcendental;
+mod trigonometry;
+mod verification;
 
 fn main() {
     let input = io_manager::read_input();
-    let equation_result = solve_equation::solve_equation(&input);
-    io_manager::print_output(equation_result, input.variable_name);
+    if input.is_system() {
+        let system_result = solve_system::solve_system(&input);
+        io_manager::print_system_output(
+            system_result,
+            input.equation(),
+            &input.unknowns(),
+            input.settings.locale,
+        );
+        return;
+    }
+    let (equation_result, verification) = match solve_equation::solve_equation(&input) {
+        Ok((result, verification)) => (Ok(result), Some(verification)),
+        Err(diagnostic) => (Err(diagnostic), None),
+    };
+    io_manager::print_output(
+        equation_result,
+        verification,
+        input.equation(),
+        input.variable_name.clone(),
+        input.settings.locale,
+        input.settings.solution_range,
+    );
 }
@@ -1,3 +1,6 @@
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
 use crate::equation_element::{
     AdditiveOperationType::{self, *},
     EquationElement::{self, *},
@@ -6,12 +9,20 @@ use crate::equation_element::{
     ValueType::{self, *},
 };
 use crate::equation_error::EquationError::{self, *};
-use crate::equation_side::EquationSide;
+use crate::radical::{self, Radical};
+use crate::scalar::Scalar;
+use crate::settings::Settings;
 use crate::term::Term;
+use crate::transcendental::{self, Transcendental};
 
+// A sum of products which is kept as a fraction. Every product is reduced when a factor is merged
+// and added to the sum over the least common multiple of their denominators
 pub struct NestedTerm {
     pub term: Term,
-    pub multiplier: Term,
+    pub denominator: Term,
+    multiplier: Term,
+    multiplier_denominator: Term,
+    factor: Option<(Term, Term)>,
     additive_operation: AdditiveOperationType,
     multiplicative_operation: MultiplicativeOperationType,
 }
@@ -20,15 +31,36 @@ impl NestedTerm {
     pub fn new() -> Self {
         Self {
             term: Term::new(),
+            denominator: Term::new_multiplier(),
             multiplier: Term::new_multiplier(),
+            multiplier_denominator: Term::new_multiplier(),
+            factor: None,
             additive_operation: Addition,
             multiplicative_operation: Multiplication,
         }
     }
 
-    pub fn push_multiplier(&mut self, equation_side_multiplier: &Term) {
+    pub fn push_multiplier(&mut self, settings: &Settings) {
+        let tolerance = &settings.tolerance;
+        let common_factor = self
+            .denominator
+            .gcd(&self.multiplier_denominator, tolerance);
+        let (term_factor, _) = self
+            .multiplier_denominator
+            .divide_polynomial(&common_factor, tolerance);
+        let (multiplier_factor, _) = self
+            .denominator
+            .divide_polynomial(&common_factor, tolerance);
+        self.term.multiply_term(&term_factor);
+        self.denominator.multiply_term(&term_factor);
+        self.multiplier.multiply_term(&multiplier_factor);
+
         for (exponent, other_coefficient) in self.multiplier.addends.iter() {
-            let coefficient = self.term.addends.entry(*exponent).or_insert(0.0);
+            let coefficient = self
+                .term
+                .addends
+                .entry(*exponent)
+                .or_insert_with(Scalar::zero);
             match self.additive_operation {
                 Addition => *coefficient += other_coefficient,
                 Subtraction => *coefficient -= other_coefficient,
@@ -36,15 +68,14 @@ impl NestedTerm {
         }
 
         self.term
-            .exceptions_in_domain
-            .extend(&self.multiplier.exceptions_in_domain);
+            .extend_exceptions(&self.multiplier, &settings.tolerance);
 
         self.multiplier = Term::new_multiplier();
-        self.multiplier.multiply_term(equation_side_multiplier);
+        self.multiplier_denominator = Term::new_multiplier();
         self.multiplicative_operation = Multiplication;
     }
 
-    pub fn multiply_value(
+    pub fn push_value(
         &mut self,
         value: &ValueType,
         previous_element: &EquationElement,
@@ -68,43 +99,106 @@ impl NestedTerm {
             }
         }
 
-        match self.multiplicative_operation {
-            Multiplication => self.multiplier.multiply_value(value),
-            Division => self.multiplier.divide_value(value)?,
-        }
+        self.factor = Some((Term::from_value(value), Term::new_multiplier()));
         Ok(())
     }
 
-    pub fn merge_term(
+    pub fn push_term(&mut self, term: Term, denominator: Term) {
+        self.factor = Some((term, denominator));
+    }
+
+    // A non-constant factor raised to p/q becomes the q-th root raised to p. The q-th root of a
+    // fraction n/d is written as the q-th root of nd^(q - 1) over d, so that only the numerator
+    // contains the root
+    pub fn raise_factor(
         &mut self,
-        term: &Term,
-        equation_side_multiplier: &mut Term,
-        other_equation_side: &mut EquationSide,
+        exponent: &Scalar,
+        radicals: &mut Vec<Radical>,
+        settings: &Settings,
     ) -> Result<(), EquationError> {
+        let (factor, denominator) = self.factor.as_mut().ok_or(InvalidOperation)?;
+        if exponent.is_negative() {
+            self.multiplicative_operation = match self.multiplicative_operation {
+                Multiplication => Division,
+                Division => Multiplication,
+            };
+        }
+        let mut exponent = exponent.abs();
+        // a nested root is registered as a root of the roots registered before
+        let constant = factor.constant().is_some()
+            && denominator.constant().is_some()
+            && !radical::depends_on_roots(factor, radicals)
+            && !radical::depends_on_roots(denominator, radicals);
+        if let (false, Scalar::Exact(value)) = (constant, &exponent) {
+            if !value.is_integer() {
+                let index = value
+                    .denom()
+                    .to_i32()
+                    .filter(|index| *index <= radical::MAX_INDEX)
+                    .ok_or(InvalidExponent)?;
+                // d^(q - 1) is not positive for even q, so d^(2q - 1) over d² is used instead
+                let (power, root_denominator) = match index % 2 == 0 {
+                    true => (2 * index - 1, 2),
+                    false => (index - 1, 1),
+                };
+                let mut radicand = factor.clone();
+                for _ in 0..power {
+                    radicand.multiply_term(denominator);
+                }
+                *factor = radical::register(&radicand, index, radicals, settings)?;
+                denominator.raise(&Scalar::from(root_denominator))?;
+                exponent = Scalar::Exact(BigRational::from(value.numer().clone()));
+            }
+        }
+        factor.raise(&exponent)?;
+        denominator.raise(&exponent)
+    }
+
+    // a constant factor raised to an exponent which depends on the variable
+    pub fn exponentiate_factor(
+        &mut self,
+        exponent: &Term,
+        transcendentals: &mut Vec<Transcendental>,
+        settings: &Settings,
+    ) -> Result<(), EquationError> {
+        let (factor, _) = self.factor.as_mut().ok_or(InvalidOperation)?;
+        let base = factor.constant().ok_or(InvalidExponent)?;
+        *factor = transcendental::exponential(&base, exponent, transcendentals, settings)?;
+        Ok(())
+    }
+
+    // multiplies the product with the factor or its reciprocal, whose numerator is excluded from
+    // the domain where it is zero
+    pub fn merge_factor(&mut self, settings: &Settings) -> Result<(), EquationError> {
+        let (factor, denominator) = match self.factor.take() {
+            Some(factor) => factor,
+            None => return Ok(()),
+        };
+
         match self.multiplicative_operation {
             Multiplication => {
-                self.multiplier.multiply_term(term);
+                self.multiplier.multiply_term(&factor);
+                self.multiplier_denominator.multiply_term(&denominator);
             }
             Division => {
-                self.multiplier.divide_term(
-                    term,
-                    &mut self.term,
-                    equation_side_multiplier,
-                    other_equation_side,
-                )?;
+                self.multiplier
+                    .add_exceptions_in_domain_of_divisor(&factor, settings)?;
+                self.multiplier.multiply_term(&denominator);
+                self.multiplier_denominator.multiply_term(&factor);
             }
         }
         self.multiplier
-            .exceptions_in_domain
-            .extend(&term.exceptions_in_domain);
+            .reduce_fraction(&mut self.multiplier_denominator, &settings.tolerance);
+        self.multiplier
+            .extend_exceptions(&factor, &settings.tolerance);
         Ok(())
     }
 
     pub fn set_operation(
         &mut self,
         operation: OperationType,
-        equation_side_multiplier: &Term,
         previous_element: &EquationElement,
+        settings: &Settings,
     ) -> Result<(), EquationError> {
         match operation {
             AdditiveOperation(additive_operation) => {
@@ -112,7 +206,7 @@ impl NestedTerm {
                     return Err(InvalidOperation);
                 }
                 if let Value(_) | ClosingParenthesis = *previous_element {
-                    self.push_multiplier(equation_side_multiplier);
+                    self.push_multiplier(settings);
                 }
                 self.additive_operation = additive_operation;
             }
@@ -123,6 +217,7 @@ impl NestedTerm {
                     return Err(InvalidOperation);
                 }
             }
+            Exponentiation => return Err(InvalidOperation),
         }
         Ok(())
     }
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+
+use crate::equation_result::EquationResult::{self, *};
+use crate::interval::{self, Bound::*, Interval};
+use crate::radical::{self, Radical};
+use crate::scalar::Scalar;
+use crate::settings::{Settings, Tolerance};
+use crate::term::Term;
+use crate::transcendental::{self, Transcendental};
+
+// the interval which is searched if the settings do not give one
+const DEFAULT_RANGE: (f64, f64) = (-100.0, 100.0);
+// the number of subintervals the range is split into when looking for sign changes
+const SAMPLES: usize = 20_000;
+const MAX_ITERATIONS: usize = 200;
+// 1/φ, by which golden section search shrinks the bracket in every step
+const GOLDEN_RATIO_CONJUGATE: f64 = 0.618_033_988_749_895;
+
+// The quotient of the term and the denominator, whose zeroes are the solutions and whose poles are
+// singularities, together with the sum of the magnitudes of its addends
+struct Function<'a> {
+    term: &'a Term,
+    denominator: &'a Term,
+    radicals: &'a [Radical],
+    transcendentals: &'a [Transcendental],
+    tolerance: &'a Tolerance,
+}
+
+impl Function<'_> {
+    fn parameters(&self, value: f64) -> Option<HashMap<String, f64>> {
+        let parameters = transcendental::parameters(self.transcendentals, value)?;
+        radical::roots(self.radicals, value, parameters, self.tolerance)
+    }
+
+    fn evaluate(&self, value: f64) -> Option<(f64, f64)> {
+        let parameters = self.parameters(value)?;
+        let (term, magnitude) = self.term.evaluate_parameters(value, &parameters);
+        let (denominator, _) = self.denominator.evaluate_parameters(value, &parameters);
+        let quotient = term / denominator;
+        quotient
+            .is_finite()
+            .then_some((quotient, magnitude / denominator.abs()))
+    }
+
+    fn value(&self, value: f64) -> Option<f64> {
+        self.evaluate(value).map(|(quotient, _)| quotient)
+    }
+
+    // the functions and the divisors are defined and no divisor vanishes
+    fn is_defined_at(&self, value: f64) -> bool {
+        self.parameters(value).is_some_and(|parameters| {
+            self.term
+                .symbolic_divisors
+                .iter()
+                .chain([self.denominator])
+                .all(|divisor| {
+                    let (divisor_value, magnitude) =
+                        divisor.evaluate_parameters(value, &parameters);
+                    divisor_value.is_finite()
+                        && !self.tolerance.is_negligible(divisor_value, magnitude)
+                })
+        })
+    }
+}
+
+// Scans the range for sign changes, which are refined by Brent's method unless the function grows
+// without bound there, and for local minima of the magnitude which touch zero. The result lists
+// every root that was found with the width of the bracket it was located in, but roots outside
+// of the range or between two samples without a sign change can be missed
+pub fn solve(
+    term: &Term,
+    denominator: &Term,
+    radicals: &[Radical],
+    transcendentals: &[Transcendental],
+    settings: &Settings,
+) -> EquationResult {
+    let function = Function {
+        term,
+        denominator,
+        radicals,
+        transcendentals,
+        tolerance: &settings.tolerance,
+    };
+    let (lower, upper) = settings.solution_range.unwrap_or(DEFAULT_RANGE);
+    let step = (upper - lower) / SAMPLES as f64;
+    let evaluations = (0..=SAMPLES)
+        .map(|index| {
+            let position = lower + step * index as f64;
+            (position, function.evaluate(position))
+        })
+        .collect::<Vec<(f64, Option<(f64, f64)>)>>();
+    let samples = evaluations
+        .iter()
+        .map(|(position, evaluation)| (*position, evaluation.map(|(value, _)| value)))
+        .collect::<Vec<(f64, Option<f64>)>>();
+    // the addends cancel at a sample where the function vanishes, which only a comparison relative
+    // to their magnitude tells apart from a function which is merely small there
+    let vanishing = evaluations
+        .iter()
+        .map(|(_, evaluation)| {
+            evaluation.is_some_and(|(value, magnitude)| {
+                value.abs() <= settings.tolerance.relative * magnitude
+            })
+        })
+        .collect::<Vec<bool>>();
+    if vanishing.iter().all(|vanishes| *vanishes) {
+        return InfiniteSolutions {
+            exceptions: Vec::new(),
+        };
+    }
+
+    // the function is zero on the whole interval between consecutive samples at which it
+    // vanishes, so such runs are solutions instead of being searched for roots
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (index, vanishes) in vanishing.iter().chain([&false]).enumerate() {
+        match (vanishes, start) {
+            (true, None) => start = Some(index),
+            (false, Some(first)) => {
+                if index - first > 1 {
+                    runs.push((first, index - 1));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    let in_run = |index: usize| {
+        runs.iter()
+            .any(|(first, last)| (*first..=*last).contains(&index))
+    };
+
+    let mut roots = Vec::new();
+    for (index, (position, value)) in samples.iter().enumerate() {
+        if in_run(index) {
+            continue;
+        }
+        if *value == Some(0.0) {
+            roots.push((*position, 0.0));
+            continue;
+        }
+        if let (Some(value), Some(&(next_position, Some(next_value)))) =
+            (value, samples.get(index + 1))
+        {
+            if value * next_value < 0.0 {
+                roots.extend(brent(
+                    &function,
+                    (*position, *value),
+                    (next_position, next_value),
+                    settings.precision,
+                ));
+            }
+        }
+        if let (Some((_, Some(previous))), Some(value), Some((next_position, Some(next)))) = (
+            index.checked_sub(1).map(|index| samples[index]),
+            value,
+            samples.get(index + 1),
+        ) {
+            let minimum = value.abs() < previous.abs()
+                && value.abs() < next.abs()
+                && value.signum() == previous.signum()
+                && value.signum() == next.signum();
+            if minimum {
+                roots.extend(touching_root(
+                    &function,
+                    position - step,
+                    *next_position,
+                    settings.precision,
+                ));
+            }
+        }
+    }
+
+    let domain = term.exceptions_in_domain.domain();
+    roots.retain(|(root, _)| {
+        function.is_defined_at(*root)
+            && !interval::intersection(
+                &domain,
+                &[Interval::point(Scalar::Approximate(*root))],
+                &settings.tolerance,
+            )
+            .is_empty()
+    });
+    if !runs.is_empty() {
+        let mut intervals = runs
+            .iter()
+            .map(|(first, last)| Interval {
+                lower: Closed(Scalar::Approximate(samples[*first].0)),
+                upper: Closed(Scalar::Approximate(samples[*last].0)),
+            })
+            .collect::<Vec<Interval>>();
+        intervals.extend(
+            roots
+                .iter()
+                .map(|(root, _)| Interval::point(Scalar::Approximate(*root))),
+        );
+        return Intervals(interval::intersection(
+            &interval::union(intervals, &settings.tolerance),
+            &domain,
+            &settings.tolerance,
+        ));
+    }
+    roots.sort_by(|(root, _), (other, _)| other.total_cmp(root));
+    roots.dedup_by(|(root, tolerance), (other, other_tolerance)| {
+        (*root - *other).abs() <= (*tolerance + *other_tolerance).max(step / 2.0)
+    });
+    NumericSolutions {
+        solutions: roots
+            .iter()
+            .map(|(root, _)| Scalar::Approximate(*root))
+            .collect(),
+        tolerances: roots.iter().map(|(_, tolerance)| *tolerance).collect(),
+        range: (lower, upper),
+    }
+}
+
+// Brent's method on a bracket with a sign change, which combines inverse quadratic interpolation
+// and secant steps with bisection. A bracket which closes around a pole instead of a root is
+// rejected as a singularity
+fn brent(
+    function: &Function,
+    (mut a, mut value_a): (f64, f64),
+    (mut b, mut value_b): (f64, f64),
+    precision: f64,
+) -> Option<(f64, f64)> {
+    let bound = value_a.abs().max(value_b.abs());
+    let (mut c, mut value_c) = (b, value_b);
+    let mut step = b - a;
+    let mut previous_step = step;
+    for _ in 0..MAX_ITERATIONS {
+        if (value_b > 0.0) == (value_c > 0.0) {
+            (c, value_c) = (a, value_a);
+            step = b - a;
+            previous_step = step;
+        }
+        if value_c.abs() < value_b.abs() {
+            (a, value_a) = (b, value_b);
+            (b, value_b) = (c, value_c);
+            (c, value_c) = (a, value_a);
+        }
+        let tolerance = precision * b.abs().max(1.0);
+        let midpoint = (c - b) / 2.0;
+        if value_b == 0.0 {
+            // an exact zero is only known up to the precision, not up to the width of the bracket
+            return Some((b, tolerance));
+        }
+        if midpoint.abs() <= tolerance {
+            return (value_b.abs() <= bound).then_some((b, midpoint.abs()));
+        }
+
+        match previous_step.abs() >= tolerance && value_a.abs() > value_b.abs() {
+            true => {
+                let s = value_b / value_a;
+                let (p, q) = match a == c {
+                    true => (2.0 * midpoint * s, 1.0 - s),
+                    false => {
+                        let q = value_a / value_c;
+                        let r = value_b / value_c;
+                        (
+                            s * (2.0 * midpoint * q * (q - r) - (b - a) * (r - 1.0)),
+                            (q - 1.0) * (r - 1.0) * (s - 1.0),
+                        )
+                    }
+                };
+                let q = match p > 0.0 {
+                    true => -q,
+                    false => q,
+                };
+                let p = p.abs();
+                let interpolation_bound =
+                    (3.0 * midpoint * q - (tolerance * q).abs()).min((previous_step * q).abs());
+                match 2.0 * p < interpolation_bound {
+                    true => {
+                        previous_step = step;
+                        step = p / q;
+                    }
+                    false => {
+                        step = midpoint;
+                        previous_step = step;
+                    }
+                }
+            }
+            false => {
+                step = midpoint;
+                previous_step = step;
+            }
+        }
+
+        (a, value_a) = (b, value_b);
+        b += match step.abs() > tolerance {
+            true => step,
+            false => tolerance.copysign(midpoint),
+        };
+        value_b = function.value(b)?;
+    }
+    None
+}
+
+// Golden section search for the minimum of the magnitude, which is a root of even multiplicity if
+// the magnitude is negligible there
+fn touching_root(
+    function: &Function,
+    mut a: f64,
+    mut b: f64,
+    precision: f64,
+) -> Option<(f64, f64)> {
+    let magnitude = |position: f64| function.value(position).map(f64::abs);
+    let mut inner_a = b - GOLDEN_RATIO_CONJUGATE * (b - a);
+    let mut inner_b = a + GOLDEN_RATIO_CONJUGATE * (b - a);
+    let mut value_a = magnitude(inner_a)?;
+    let mut value_b = magnitude(inner_b)?;
+    for _ in 0..MAX_ITERATIONS {
+        if b - a <= precision * a.abs().max(b.abs()).max(1.0) {
+            break;
+        }
+        match value_a < value_b {
+            true => {
+                b = inner_b;
+                (inner_b, value_b) = (inner_a, value_a);
+                inner_a = b - GOLDEN_RATIO_CONJUGATE * (b - a);
+                value_a = magnitude(inner_a)?;
+            }
+            false => {
+                a = inner_a;
+                (inner_a, value_a) = (inner_b, value_b);
+                inner_b = a + GOLDEN_RATIO_CONJUGATE * (b - a);
+                value_b = magnitude(inner_b)?;
+            }
+        }
+    }
+    let root = (a + b) / 2.0;
+    let (value, magnitude) = function.evaluate(root)?;
+    // the function is flat at such a root, so rounding errors move it by about the square root of
+    // the precision
+    function
+        .tolerance
+        .is_negligible(value, magnitude)
+        .then_some((root, precision.sqrt() * root.abs().max(1.0)))
+}
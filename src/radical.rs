@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use crate::equation_element::RelationType::{self, *};
+use crate::equation_error::EquationError::{self, *};
+use crate::equation_result::EquationResult::{self, *};
+use crate::exceptions_in_domain::ExceptionsInDomain::*;
+use crate::expression::Expression;
+use crate::inequality;
+use crate::interval;
+use crate::scalar::Scalar;
+use crate::settings::{Settings, Tolerance};
+use crate::term::Term;
+
+// the highest index of a root which is eliminated by raising to its power
+pub const MAX_INDEX: i32 = 6;
+
+// the principal root of the radicand, whose coefficients can contain the roots registered before
+#[derive(Clone, Debug)]
+pub struct Radical {
+    pub radicand: Term,
+    pub index: i32,
+}
+
+impl Radical {
+    // the symbolic parameter standing for the root, which cannot clash with an identifier
+    fn name(position: usize) -> String {
+        format!("√{position}")
+    }
+}
+
+// whether a coefficient contains one of the roots registered so far, so that a term which is
+// constant in the variable still depends on it
+pub fn depends_on_roots(term: &Term, radicals: &[Radical]) -> bool {
+    term.addends.values().any(|coefficient| match coefficient {
+        Scalar::Symbolic(expression) => {
+            (0..radicals.len()).any(|position| expression.contains(&Radical::name(position)))
+        }
+        _ => false,
+    })
+}
+
+// Replaces the root of the radicand by a new symbolic parameter. For even indices the domain is
+// restricted to where the radicand is not negative
+pub fn register(
+    radicand: &Term,
+    index: i32,
+    radicals: &mut Vec<Radical>,
+    settings: &Settings,
+) -> Result<Term, EquationError> {
+    let polynomial = radicand.lowest_exponent(&settings.tolerance) >= 0
+        && radicand
+            .addends
+            .values()
+            .all(|coefficient| match coefficient {
+                Scalar::Symbolic(expression) => expression.denominator().as_constant().is_some(),
+                _ => true,
+            });
+    if index > MAX_INDEX || !polynomial {
+        return Err(InvalidExponent);
+    }
+
+    let mut root = Term::new_multiplier();
+    root.multiply_constant(&Scalar::from(Expression::parameter(&Radical::name(
+        radicals.len(),
+    ))));
+    root.extend_exceptions(radicand, &settings.tolerance);
+    if index % 2 == 0 && !radicand.is_symbolic() {
+        let domain = match inequality::solve(
+            radicand,
+            &Term::new_multiplier(),
+            GreaterThanOrEqual,
+            settings,
+        )? {
+            Intervals(intervals) => intervals,
+            _ => Vec::new(),
+        };
+        root.exceptions_in_domain.extend(
+            &Restricted {
+                exceptions: HashMap::new(),
+                domain,
+            },
+            &settings.tolerance,
+        );
+    }
+    radicals.push(Radical {
+        radicand: radicand.clone(),
+        index,
+    });
+    Ok(root)
+}
+
+// Raising to powers turns the equation into a polynomial one whose real zeroes are the candidates
+// for solutions. Together with the bounds of the domain and the zeroes of the radicands and the
+// divisors they split the real line into intervals on which the original relation is checked
+pub fn solve(
+    term: &Term,
+    denominator: &Term,
+    radicals: &[Radical],
+    relation: RelationType,
+    settings: &Settings,
+) -> Result<EquationResult, EquationError> {
+    let polynomial = eliminate(term, radicals)?;
+    let identity = polynomial.degree(&settings.tolerance).is_none();
+    let candidates = match identity {
+        true => Vec::new(),
+        false => polynomial.real_zeroes(settings)?,
+    };
+
+    let mut critical_points = candidates.clone();
+    critical_points.append(&mut inequality::boundary_points(term, settings)?);
+    let divisors = radicals
+        .iter()
+        .map(|radical| &radical.radicand)
+        .chain(&term.symbolic_divisors)
+        .chain([denominator]);
+    for divisor in divisors {
+        let divisor = eliminate(divisor, radicals)?;
+        if divisor.degree(&settings.tolerance).is_some() {
+            critical_points.append(&mut divisor.real_zeroes(settings)?);
+        }
+    }
+    critical_points.sort_by(|value, other| value.to_f64().total_cmp(&other.to_f64()));
+    critical_points.dedup_by(|value, other| {
+        settings
+            .tolerance
+            .approximately_equal(value.to_f64(), other.to_f64())
+    });
+
+    let holds = |value: f64| {
+        roots(radicals, value, HashMap::new(), &settings.tolerance).is_some_and(|roots| {
+            inequality::holds_at(
+                term,
+                denominator,
+                relation,
+                value,
+                &roots,
+                &settings.tolerance,
+            )
+        })
+    };
+    let solution_set = interval::intersection(
+        &inequality::sample(
+            &critical_points,
+            relation,
+            identity,
+            holds,
+            &settings.tolerance,
+        ),
+        &term.exceptions_in_domain.domain(),
+        &settings.tolerance,
+    );
+    Ok(EquationResult::from_candidates(
+        solution_set,
+        candidates,
+        relation,
+        &settings.tolerance,
+    ))
+}
+
+// The polynomial in the variable which is zero wherever the term is zero. Each root is eliminated
+// by the determinant of the multiplication with the term in the basis of the powers of the root,
+// which is the product of the term over all conjugate roots
+fn eliminate(term: &Term, radicals: &[Radical]) -> Result<Term, EquationError> {
+    let mut polynomial = term.clear_denominators();
+    for (position, radical) in radicals.iter().enumerate().rev() {
+        let coefficients = polynomial.coefficients_of(&Radical::name(position));
+        if coefficients.keys().all(|power| *power == 0) {
+            continue;
+        }
+
+        let index = radical.index as usize;
+        let mut reduced = vec![Term::new(); index];
+        for (power, mut coefficient) in coefficients {
+            for _ in 0..power / radical.index {
+                coefficient.multiply_term(&radical.radicand);
+            }
+            let power = power as usize % index;
+            reduced[power].add_term(&coefficient);
+        }
+
+        let mut matrix = vec![vec![Term::new(); index]; index];
+        for column in 0..index {
+            for (power, coefficient) in reduced.iter().enumerate() {
+                let mut entry = coefficient.clone();
+                if power + column >= index {
+                    entry.multiply_term(&radical.radicand);
+                }
+                matrix[(power + column) % index][column] = entry;
+            }
+        }
+        polynomial = determinant(&matrix).clear_denominators();
+    }
+
+    match polynomial.is_symbolic() {
+        true => Err(SymbolicParameters),
+        false => Ok(polynomial),
+    }
+}
+
+// expansion along the first column
+fn determinant(matrix: &[Vec<Term>]) -> Term {
+    if matrix.len() == 1 {
+        return matrix[0][0].clone();
+    }
+
+    let mut expansion = Term::new();
+    for (row, entries) in matrix.iter().enumerate() {
+        if entries[0].addends.values().all(Scalar::is_zero) {
+            continue;
+        }
+        let minor = matrix
+            .iter()
+            .enumerate()
+            .filter(|(other_row, _)| *other_row != row)
+            .map(|(_, entries)| entries[1..].to_vec())
+            .collect::<Vec<Vec<Term>>>();
+        let mut product = determinant(&minor);
+        product.multiply_term(&entries[0]);
+        if row % 2 == 1 {
+            product.multiply_constant(&Scalar::from(-1));
+        }
+        expansion.add_term(&product);
+    }
+    expansion
+}
+
+// the values of the principal roots at the value added to the known parameters, which do not
+// exist if an even root has a negative radicand
+pub fn roots(
+    radicals: &[Radical],
+    value: f64,
+    mut roots: HashMap<String, f64>,
+    tolerance: &Tolerance,
+) -> Option<HashMap<String, f64>> {
+    for (position, radical) in radicals.iter().enumerate() {
+        let (radicand, magnitude) = radical.radicand.evaluate_parameters(value, &roots);
+        let radicand = match tolerance.is_negligible(radicand, magnitude) {
+            true => 0.0,
+            false => radicand,
+        };
+        if radicand < 0.0 && radical.index % 2 == 0 {
+            return None;
+        }
+        let root = radicand.signum() * radicand.abs().powf(1.0 / radical.index as f64);
+        roots.insert(Radical::name(position), root);
+    }
+    Some(roots)
+}
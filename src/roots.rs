@@ -0,0 +1,336 @@
+use num_rational::BigRational;
+use num_traits::Signed;
+use std::f64::consts::PI;
+
+use crate::complex::Complex;
+use crate::scalar::Scalar::{self, *};
+use crate::surd::Surd;
+
+const MAX_ITERATIONS: usize = 500;
+
+// linear and quadratic roots of exact coefficients. Irrational quadratic roots are kept as surds
+pub fn exact(coefficients: &[Scalar]) -> Option<Vec<Scalar>> {
+    let coefficients = coefficients
+        .iter()
+        .map(|coefficient| match coefficient {
+            Exact(value) => Some(value),
+            _ => None,
+        })
+        .collect::<Option<Vec<&BigRational>>>()?;
+
+    match coefficients[..] {
+        [b, a] => Some(vec![Exact(-b / a)]),
+        [c, b, a] => {
+            let discriminant = b * b - BigRational::from_integer(4.into()) * a * c;
+            if discriminant.is_negative() {
+                return None;
+            }
+            let denominator = BigRational::from_integer(2.into()) * a;
+            let vertex = -b / &denominator;
+            match Scalar::Exact(discriminant.clone()).sqrt() {
+                Exact(root) => Some(vec![
+                    Exact(&vertex + &root / &denominator),
+                    Exact(&vertex - &root / &denominator),
+                ]),
+                _ => {
+                    let root = Surd::sqrt(&discriminant);
+                    Some(vec![
+                        Scalar::Surd(root.scale(&denominator.recip()).shift(&vertex)),
+                        Scalar::Surd(root.scale(&-denominator.recip()).shift(&vertex)),
+                    ])
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+// Equal roots are merged into one root with its multiplicity. Approximate roots are equal if they
+// are closer than their error bounds, which grow for multiple roots, and are replaced by their
+// mean. Rounding can also split a multiple real root into a real root and complex conjugate pairs
+// around it, which are merged into the real root and removed from the complex roots
+pub fn group(
+    mut roots: Vec<Scalar>,
+    complex_roots: &mut Vec<Complex>,
+    coefficients: &[f64],
+) -> Vec<(Scalar, usize)> {
+    roots.sort_by(|value, other| other.to_f64().total_cmp(&value.to_f64()));
+    let mut groups: Vec<(Scalar, usize)> = Vec::new();
+    for root in roots {
+        let equal = groups
+            .last()
+            .is_some_and(|(value, _)| match (value, &root) {
+                (Approximate(value), Approximate(root)) => {
+                    (value - root).abs()
+                        <= error_bound(coefficients, *value) + error_bound(coefficients, *root)
+                }
+                (value, root) => value == root,
+            });
+        match (equal, groups.last_mut()) {
+            (true, Some((value, multiplicity))) => {
+                if let (Approximate(mean), Approximate(root)) = (&mut *value, &root) {
+                    *mean += (root - *mean) / (*multiplicity + 1) as f64;
+                }
+                *multiplicity += 1;
+            }
+            _ => groups.push((root, 1)),
+        }
+    }
+
+    complex_roots.retain(|root| {
+        let close = groups.iter_mut().find(|(value, _)| match value {
+            Approximate(value) => {
+                (*root - Complex::from(*value)).norm() <= error_bound(coefficients, *value)
+            }
+            _ => false,
+        });
+        match close {
+            Some((Approximate(mean), multiplicity)) => {
+                *mean += 2.0 * (root.real - *mean) / (*multiplicity + 2) as f64;
+                *multiplicity += 2;
+                false
+            }
+            _ => true,
+        }
+    });
+    groups
+}
+
+pub fn linear(a: f64, b: f64) -> Vec<Complex> {
+    vec![Complex::from(-b / a)]
+}
+
+// The root of the quadratic with the larger magnitude is computed with the sign which avoids
+// cancellation and the other one from their product c/a, since -b ± √D loses digits if b² ≫ 4ac
+pub fn quadratic(a: f64, b: f64, c: f64) -> Vec<Complex> {
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        let real = -b / (2.0 * a);
+        let imaginary = (-discriminant).sqrt() / (2.0 * a);
+        return vec![
+            Complex::new(real, imaginary),
+            Complex::new(real, -imaginary),
+        ];
+    }
+
+    let q = -(b + discriminant.sqrt().copysign(b)) / 2.0;
+    if q == 0.0 {
+        return vec![Complex::from(0.0); 2];
+    }
+    vec![Complex::from(q / a), Complex::from(c / q)]
+}
+
+// Cardano's method on the depressed cubic t^3 + pt + q with x = t - b/3a
+pub fn cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<Complex> {
+    let shift = Complex::from(-b / (3.0 * a));
+    let p = (3.0 * a * c - b * b) / (3.0 * a * a);
+    let q = (2.0 * b * b * b - 9.0 * a * b * c + 27.0 * a * a * d) / (27.0 * a * a * a);
+
+    depressed_cubic(p, q)
+        .into_iter()
+        .map(|root| root + shift)
+        .collect()
+}
+
+fn depressed_cubic(p: f64, q: f64) -> Vec<Complex> {
+    let discriminant = q * q / 4.0 + p * p * p / 27.0;
+    if discriminant > 0.0 {
+        let u = (-q / 2.0 + discriminant.sqrt()).cbrt();
+        let v = (-q / 2.0 - discriminant.sqrt()).cbrt();
+        let imaginary = 3.0_f64.sqrt() / 2.0 * (u - v);
+        return vec![
+            Complex::from(u + v),
+            Complex::new(-(u + v) / 2.0, imaginary),
+            Complex::new(-(u + v) / 2.0, -imaginary),
+        ];
+    }
+    if discriminant == 0.0 {
+        if p == 0.0 {
+            return vec![Complex::from(0.0); 3];
+        }
+        return vec![
+            Complex::from(3.0 * q / p),
+            Complex::from(-3.0 * q / (2.0 * p)),
+            Complex::from(-3.0 * q / (2.0 * p)),
+        ];
+    }
+
+    // casus irreducibilis: three distinct real roots which are found with the trigonometric form
+    // instead of taking cube roots of complex numbers
+    let radius = 2.0 * (-p / 3.0).sqrt();
+    let angle = ((3.0 * q / (2.0 * p)) * (-3.0 / p).sqrt())
+        .clamp(-1.0, 1.0)
+        .acos()
+        / 3.0;
+    (0..3)
+        .map(|k| Complex::from(radius * (angle - 2.0 * PI * k as f64 / 3.0).cos()))
+        .collect()
+}
+
+// Ferrari's method on the depressed quartic y^4 + py^2 + qy + r with x = y - b/4a
+pub fn quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<Complex> {
+    let shift = Complex::from(-b / (4.0 * a));
+    let p = (8.0 * a * c - 3.0 * b * b) / (8.0 * a * a);
+    let q = (b * b * b - 4.0 * a * b * c + 8.0 * a * a * d) / (8.0 * a * a * a);
+    let r = (-3.0 * b * b * b * b + 256.0 * a * a * a * e - 64.0 * a * a * b * d
+        + 16.0 * a * b * b * c)
+        / (256.0 * a * a * a * a);
+
+    depressed_quartic(p, q, r)
+        .into_iter()
+        .map(|root| root + shift)
+        .collect()
+}
+
+fn depressed_quartic(p: f64, q: f64, r: f64) -> Vec<Complex> {
+    if q == 0.0 {
+        return quadratic(1.0, p, r)
+            .into_iter()
+            .flat_map(|square| {
+                let root = square.sqrt();
+                [root, Complex::from(0.0) - root]
+            })
+            .collect();
+    }
+
+    // the resolvent cubic 8m^3 + 8pm^2 + (2p^2 - 8r)m - q^2 always has a positive root because it
+    // is negative at m = 0
+    let m = cubic(8.0, 8.0 * p, 2.0 * p * p - 8.0 * r, -q * q)
+        .into_iter()
+        .filter(Complex::is_real)
+        .map(|root| root.real)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let s = (2.0 * m).sqrt();
+
+    let mut roots = quadratic(1.0, -s, p / 2.0 + m + q / (2.0 * s));
+    roots.append(&mut quadratic(1.0, s, p / 2.0 + m - q / (2.0 * s)));
+    roots
+}
+
+// Aberth–Ehrlich iteration approximating all complex roots of the polynomial at once. The
+// coefficients are ordered by exponent and roots within the precision of the real axis are
+// returned as real roots
+pub fn polynomial(coefficients: &[f64], precision: f64) -> Option<Vec<Complex>> {
+    let degree = coefficients.len() - 1;
+    let leading_coefficient = coefficients[degree];
+    let radius = 2.0
+        * (0..degree)
+            .map(|exponent| {
+                (coefficients[exponent] / leading_coefficient)
+                    .abs()
+                    .powf(1.0 / (degree - exponent) as f64)
+            })
+            .fold(0.0, f64::max);
+    let mut roots = (0..degree)
+        .map(|k| Complex::from_polar(radius, 2.0 * PI * k as f64 / degree as f64 + 0.4))
+        .collect::<Vec<Complex>>();
+
+    let mut converged = false;
+    for _ in 0..MAX_ITERATIONS {
+        converged = true;
+        for i in 0..degree {
+            let (value, derivative) = evaluate(coefficients, roots[i]);
+            if value.norm() <= rounding_error(coefficients, roots[i].norm()) {
+                continue;
+            }
+
+            let ratio = value / derivative;
+            let repulsion = (0..degree)
+                .filter(|j| *j != i)
+                .fold(Complex::new(0.0, 0.0), |sum, j| {
+                    sum + Complex::new(1.0, 0.0) / (roots[i] - roots[j])
+                });
+            let correction = ratio / (Complex::new(1.0, 0.0) - ratio * repulsion);
+            if !correction.real.is_finite() || !correction.imaginary.is_finite() {
+                roots[i] = roots[i] + Complex::new(precision, precision);
+                converged = false;
+                continue;
+            }
+
+            roots[i] = roots[i] - correction;
+            if correction.norm() > precision * roots[i].norm().max(1.0) {
+                converged = false;
+            }
+        }
+        if converged {
+            break;
+        }
+    }
+    if !converged {
+        return None;
+    }
+
+    Some(
+        roots
+            .into_iter()
+            .map(
+                |root| match root.imaginary.abs() <= precision.sqrt() * root.norm().max(1.0) {
+                    true => Complex::from(polish(coefficients, root.real)),
+                    false => root,
+                },
+            )
+            .collect(),
+    )
+}
+
+fn evaluate(coefficients: &[f64], x: Complex) -> (Complex, Complex) {
+    let mut value = Complex::new(0.0, 0.0);
+    let mut derivative = Complex::new(0.0, 0.0);
+    for coefficient in coefficients.iter().rev() {
+        derivative = derivative * x + value;
+        value = value * x + Complex::new(*coefficient, 0.0);
+    }
+    (value, derivative)
+}
+
+fn rounding_error(coefficients: &[f64], radius: f64) -> f64 {
+    let magnitude = coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |sum, coefficient| sum * radius + coefficient.abs());
+    4.0 * coefficients.len() as f64 * f64::EPSILON * magnitude
+}
+
+// a few Newton steps on the real polynomial which are only kept while they reduce the residual
+pub fn polish(coefficients: &[f64], mut root: f64) -> f64 {
+    let residual = |x: f64| evaluate(coefficients, Complex::new(x, 0.0));
+    let (mut value, mut derivative) = residual(root);
+    for _ in 0..3 {
+        if derivative.real == 0.0 {
+            break;
+        }
+        let candidate = root - value.real / derivative.real;
+        let (candidate_value, candidate_derivative) = residual(candidate);
+        if candidate_value.real.abs() >= value.real.abs() {
+            break;
+        }
+        root = candidate;
+        value = candidate_value;
+        derivative = candidate_derivative;
+    }
+    root
+}
+
+// An estimate of the distance of a real root to the exact root of the polynomial. The residual
+// and the rounding error of evaluating the polynomial are divided by its Taylor coefficients at
+// the root, so the k-th coefficient bounds the error of a root of multiplicity k and the estimate
+// is the smallest of these bounds
+pub fn error_bound(coefficients: &[f64], root: f64) -> f64 {
+    let mut taylor_coefficients = coefficients.to_vec();
+    for start in 0..taylor_coefficients.len() {
+        for index in (start..taylor_coefficients.len() - 1).rev() {
+            taylor_coefficients[index] += root * taylor_coefficients[index + 1];
+        }
+    }
+    let uncertainty = taylor_coefficients[0].abs() + rounding_error(coefficients, root.abs());
+    if uncertainty == 0.0 {
+        return 0.0;
+    }
+    taylor_coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, coefficient)| **coefficient != 0.0)
+        .map(|(order, coefficient)| (uncertainty / coefficient.abs()).powf(1.0 / order as f64))
+        .fold(f64::INFINITY, f64::min)
+}
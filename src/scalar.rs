@@ -0,0 +1,297 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::expression::Expression;
+use crate::locale::Locale;
+use crate::settings::Tolerance;
+use crate::surd;
+
+use Scalar::*;
+
+// exponents above this bound are evaluated approximately to keep exact numbers reasonably small
+const MAX_EXACT_EXPONENT: i32 = 1024;
+// the largest denominator of fractions which approximate values are recognized as
+const MAX_FRACTION_DENOMINATOR: i32 = 12;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Scalar {
+    Exact(BigRational),
+    Surd(surd::Surd),
+    Approximate(f64),
+    Symbolic(Expression),
+}
+
+impl Scalar {
+    pub fn zero() -> Self {
+        Exact(BigRational::zero())
+    }
+
+    pub fn one() -> Self {
+        Exact(BigRational::one())
+    }
+
+    pub fn parse(value: &str, exact: bool) -> Option<Self> {
+        // a fraction such as the 1/2 read from ½
+        if let Some((numerator, denominator)) = value.split_once('/') {
+            let denominator = Scalar::parse(denominator, exact)?;
+            if denominator.is_zero() {
+                return None;
+            }
+            return Some(&Scalar::parse(numerator, exact)? / &denominator);
+        }
+        if exact {
+            if let Some(value) = parse_decimal(value) {
+                return Some(Exact(value));
+            }
+        }
+        value.parse::<f64>().ok().map(Approximate)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Exact(value) => value.to_f64().unwrap_or(f64::NAN),
+            Surd(value) => value.to_f64(),
+            Approximate(value) => *value,
+            Symbolic(_) => f64::NAN,
+        }
+    }
+
+    pub fn is_symbolic(&self) -> bool {
+        matches!(self, Symbolic(_))
+    }
+
+    pub fn is_surd(&self) -> bool {
+        matches!(self, Surd(_))
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Exact(_))
+    }
+
+    pub fn is_approximate(&self) -> bool {
+        matches!(self, Approximate(_))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Exact(value) => value.is_zero(),
+            Surd(_) | Symbolic(_) => false,
+            Approximate(value) => *value == 0.0,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        match self {
+            Exact(value) => value.is_negative(),
+            Surd(value) => value.to_f64() < 0.0,
+            Approximate(value) => *value < 0.0,
+            Symbolic(_) => false,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Exact(value) => value.is_integer(),
+            Surd(_) | Symbolic(_) => false,
+            Approximate(value) => value.fract() == 0.0,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        match self {
+            Exact(value) => Exact(value.abs()),
+            Surd(value) if value.to_f64() < 0.0 => Surd(value.scale(&-BigRational::one())),
+            Surd(value) => Surd(value.clone()),
+            Approximate(value) => Approximate(value.abs()),
+            Symbolic(value) => Symbolic(value.clone()),
+        }
+    }
+
+    pub fn pow(&self, exponent: &Scalar) -> Option<Self> {
+        if let (Symbolic(base), Exact(exponent)) = (self, exponent) {
+            return exponent
+                .is_integer()
+                .then(|| exponent.to_integer().to_i32())
+                .flatten()
+                .filter(|exponent| exponent.abs() <= MAX_EXACT_EXPONENT)
+                .map(|exponent| Scalar::from(base.pow(exponent)));
+        }
+        if self.is_symbolic() || exponent.is_symbolic() {
+            return None;
+        }
+        if let (Exact(base), Exact(exponent)) = (self, exponent) {
+            if let Some(exponent) = exponent
+                .is_integer()
+                .then(|| exponent.to_integer().to_i32())
+                .flatten()
+                .filter(|exponent| exponent.abs() <= MAX_EXACT_EXPONENT)
+            {
+                if base.is_zero() && exponent < 0 {
+                    return None;
+                }
+                return Some(Exact(base.pow(exponent)));
+            }
+            if !base.is_negative() && exponent == &BigRational::new(1.into(), 2.into()) {
+                return Some(self.sqrt());
+            }
+        }
+
+        let power = self.to_f64().powf(exponent.to_f64());
+        (!power.is_nan()).then_some(Approximate(power))
+    }
+
+    // exact if the numerator and the denominator are perfect squares
+    pub fn sqrt(&self) -> Self {
+        if let Exact(value) = self {
+            if !value.is_negative() {
+                let numerator = value.numer().sqrt();
+                let denominator = value.denom().sqrt();
+                if &(&numerator * &numerator) == value.numer()
+                    && &(&denominator * &denominator) == value.denom()
+                {
+                    return Exact(BigRational::new(numerator, denominator));
+                }
+            }
+        }
+        Approximate(self.to_f64().sqrt())
+    }
+}
+
+// the fraction with a small denominator which the value is a rounded form of
+pub fn approximate_fraction(value: f64, tolerance: &Tolerance) -> Option<BigRational> {
+    if !value.is_finite() {
+        return None;
+    }
+    (1..=MAX_FRACTION_DENOMINATOR).find_map(|denominator| {
+        let numerator = (value * denominator as f64).round();
+        match tolerance.approximately_equal(value * denominator as f64, numerator) {
+            true => Some(BigRational::new(
+                BigInt::from_f64(numerator)?,
+                BigInt::from(denominator),
+            )),
+            false => None,
+        }
+    })
+}
+
+fn parse_decimal(value: &str) -> Option<BigRational> {
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (
+            mantissa,
+            exponent
+                .parse::<i32>()
+                .ok()
+                .filter(|exponent| exponent.abs() <= MAX_EXACT_EXPONENT)?,
+        ),
+        None => (value, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integer.is_empty() && fraction.is_empty()
+        || !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|digit| digit.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let digits = format!("{integer}{fraction}").parse::<BigInt>().ok()?;
+    let denominator = num_traits::pow(BigInt::from(10), fraction.len());
+    let power = BigRational::from_integer(BigInt::from(10)).pow(exponent);
+    Some(BigRational::new(digits, denominator) * power)
+}
+
+impl From<i32> for Scalar {
+    fn from(value: i32) -> Self {
+        Exact(BigRational::from_integer(BigInt::from(value)))
+    }
+}
+
+impl Scalar {
+    pub fn format(&self, locale: Locale) -> String {
+        match self {
+            Exact(value) if value.is_integer() => locale.format_number(value.numer()),
+            Exact(value) => format!(
+                "{}/{}",
+                locale.format_number(value.numer()),
+                locale.format_number(value.denom())
+            ),
+            Surd(value) => value.format(locale),
+            Approximate(value) => locale.format_number(value),
+            Symbolic(value) => value.format(locale),
+        }
+    }
+}
+
+impl Display for Scalar {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.format(Locale::Neutral))
+    }
+}
+
+macro_rules! impl_operation {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $operator:tt) => {
+        impl $trait<&Scalar> for &Scalar {
+            type Output = Scalar;
+
+            fn $method(self, other: &Scalar) -> Scalar {
+                match (self, other) {
+                    (Exact(value), Exact(other_value)) => Exact(value $operator other_value),
+                    (Symbolic(_), _) | (_, Symbolic(_)) => {
+                        Scalar::from(&Expression::from(self) $operator &Expression::from(other))
+                    }
+                    _ => Approximate(self.to_f64() $operator other.to_f64()),
+                }
+            }
+        }
+
+        impl $assign_trait<&Scalar> for Scalar {
+            fn $assign_method(&mut self, other: &Scalar) {
+                *self = &*self $operator other;
+            }
+        }
+    };
+}
+
+impl_operation!(Add, add, AddAssign, add_assign, +);
+impl_operation!(Sub, sub, SubAssign, sub_assign, -);
+impl_operation!(Mul, mul, MulAssign, mul_assign, *);
+
+// exact division by zero would panic, so it follows the floating point semantics instead
+impl Div<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn div(self, other: &Scalar) -> Scalar {
+        match (self, other) {
+            (Exact(value), Exact(other_value)) if !other_value.is_zero() => {
+                Exact(value / other_value)
+            }
+            (Symbolic(_), _) | (_, Symbolic(_)) if !other.is_zero() => {
+                Scalar::from(&Expression::from(self) / &Expression::from(other))
+            }
+            _ => Approximate(self.to_f64() / other.to_f64()),
+        }
+    }
+}
+
+impl DivAssign<&Scalar> for Scalar {
+    fn div_assign(&mut self, other: &Scalar) {
+        *self = &*self / other;
+    }
+}
+
+impl Neg for &Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        match self {
+            Exact(value) => Exact(-value),
+            Surd(value) => Surd(value.scale(&-BigRational::one())),
+            Approximate(value) => Approximate(-value),
+            Symbolic(value) => Symbolic(-value),
+        }
+    }
+}
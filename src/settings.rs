@@ -0,0 +1,95 @@
+use RootFinding::*;
+
+use crate::locale::Locale;
+
+const MAX_CLOSED_FORM_DEGREE: i32 = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub root_finding: RootFinding,
+    pub max_degree: Option<i32>,
+    pub precision: f64,
+    pub tolerance: Tolerance,
+    pub complex_solutions: bool,
+    // whether approximate roots of polynomials are reported with an estimate of their error
+    pub error_bounds: bool,
+    pub exact_arithmetic: bool,
+    pub locale: Locale,
+    pub angle_unit: AngleUnit,
+    // the interval in which the members of periodic solution families are listed and numeric
+    // solutions are searched
+    pub solution_range: Option<(f64, f64)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RootFinding {
+    // closed forms up to degree 4, numeric root finding above
+    Automatic,
+    // closed forms only, higher degrees are rejected
+    ClosedForm,
+    // exact formulas up to degree 2, numeric root finding above
+    Numeric,
+}
+
+// the absolute and relative tolerance below which approximate values count as zero
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AngleUnit {
+    Radians,
+    Degrees,
+}
+
+impl Settings {
+    pub fn degree_limit(&self) -> i32 {
+        let limit = match self.root_finding {
+            ClosedForm => MAX_CLOSED_FORM_DEGREE,
+            Automatic | Numeric => i32::MAX,
+        };
+        match self.max_degree {
+            Some(max_degree) => limit.min(max_degree),
+            None => limit,
+        }
+    }
+}
+
+impl Tolerance {
+    // the value is negligible compared to the magnitude of the values it was computed from
+    pub fn is_negligible(&self, value: f64, magnitude: f64) -> bool {
+        value.abs() <= self.absolute + self.relative * magnitude.abs()
+    }
+
+    pub fn approximately_equal(&self, value: f64, other: f64) -> bool {
+        self.is_negligible(value - other, value.abs().max(other.abs()))
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            absolute: 1e-14,
+            relative: 1e-12,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            root_finding: Automatic,
+            max_degree: None,
+            precision: 1e-12,
+            tolerance: Tolerance::default(),
+            complex_solutions: false,
+            error_bounds: false,
+            exact_arithmetic: true,
+            locale: Locale::Neutral,
+            angle_unit: AngleUnit::Radians,
+            solution_range: None,
+        }
+    }
+}
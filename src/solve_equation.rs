@@ -1,10 +1,27 @@
+use crate::absolute_value;
 use crate::equation::Equation;
-use crate::equation_error::EquationError;
+use crate::equation_element::EquationElement::OpeningAbsoluteValue;
+use crate::equation_error::Diagnostic;
 use crate::equation_result::EquationResult;
 use crate::token_stream::EquationInput;
+use crate::verification::{self, OriginalEquation, Verification};
 
-pub fn solve_equation(input: &EquationInput) -> Result<EquationResult, EquationError> {
+// The solutions are substituted into the equation as it was entered, which catches candidates
+// that clearing fractions, squaring radicals or splitting absolute values got wrong
+pub fn solve_equation(input: &EquationInput) -> Result<(EquationResult, Verification), Diagnostic> {
     let token_stream = input.token_stream()?;
-    let equation = Equation::generate(token_stream)?;
-    equation.set_zero().zeroes()
+    let original_equation = OriginalEquation::parse(&token_stream);
+    let result = match token_stream
+        .iter()
+        .any(|token| token.element == OpeningAbsoluteValue)
+    {
+        true => absolute_value::solve(token_stream, &input.settings)?,
+        false => Equation::generate(token_stream, &input.settings)?.solve(&input.settings)?,
+    };
+    Ok(match original_equation {
+        Some(original_equation) => {
+            verification::verify(result, &original_equation, &input.settings)
+        }
+        None => (result, Verification::default()),
+    })
 }
@@ -0,0 +1,9 @@
+use crate::equation_error::Diagnostic;
+use crate::linear_system::LinearSystem;
+use crate::system_result::SystemResult;
+use crate::token_stream::EquationInput;
+
+pub fn solve_system(input: &EquationInput) -> Result<SystemResult, Diagnostic> {
+    let system = LinearSystem::generate(input)?;
+    Ok(system.solve(&input.settings))
+}
@@ -0,0 +1,50 @@
+// the byte and the character range of a region in the input
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub start_character: usize,
+    pub end_character: usize,
+}
+
+impl Span {
+    pub fn new(start: (usize, usize), end: (usize, usize)) -> Self {
+        Self {
+            start: start.0,
+            end: end.0,
+            start_character: start.1,
+            end_character: end.1,
+        }
+    }
+
+    pub fn join(&self, other: &Span) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+            start_character: self.start_character.min(other.start_character),
+            end_character: self.end_character.max(other.end_character),
+        }
+    }
+
+    pub fn shift(&self, bytes: usize, characters: usize) -> Self {
+        Self {
+            start: self.start + bytes,
+            end: self.end + bytes,
+            start_character: self.start_character + characters,
+            end_character: self.end_character + characters,
+        }
+    }
+
+    // a caret under the first character followed by tildes under the rest of the region
+    pub fn underline(&self) -> String {
+        let length = self
+            .end_character
+            .saturating_sub(self.start_character)
+            .max(1);
+        format!(
+            "{}^{}",
+            " ".repeat(self.start_character),
+            "~".repeat(length - 1)
+        )
+    }
+}
@@ -0,0 +1,182 @@
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+use crate::scalar::Scalar;
+use crate::settings::Tolerance;
+use crate::term::Term;
+
+// the largest denominator which is tried for rational roots
+const MAX_DENOMINATOR: u32 = 1000;
+
+// The Sturm sequence of the square-free part of a polynomial with exact coefficients, starting
+// with the polynomial and its derivative and continuing with the negated remainders of their
+// Euclidean algorithm. The number of sign changes along the sequence drops by one at every
+// distinct real root of the polynomial and nowhere else. Each member is scaled to integer
+// coefficients ordered by exponent, which keeps its signs and avoids reducing fractions when it
+// is evaluated
+pub fn sequence(polynomial: &Term, tolerance: &Tolerance) -> Option<Vec<Vec<BigInt>>> {
+    if !polynomial.addends.values().all(Scalar::is_exact) {
+        return None;
+    }
+    let square_free = match polynomial.degree(tolerance) {
+        Some(1..) => {
+            let repeated = polynomial.gcd(&polynomial.derivative(), tolerance);
+            polynomial.divide_polynomial(&repeated, tolerance).0
+        }
+        _ => return Some(vec![integer_coefficients(polynomial, tolerance)]),
+    };
+
+    let mut sequence = vec![square_free.clone(), square_free.derivative()];
+    loop {
+        let mut remainder = sequence[sequence.len() - 2]
+            .divide_polynomial(&sequence[sequence.len() - 1], tolerance)
+            .1;
+        if remainder.degree(tolerance).is_none() {
+            break;
+        }
+        remainder.multiply_constant(&Scalar::from(-1));
+        sequence.push(remainder);
+    }
+    Some(
+        sequence
+            .iter()
+            .map(|term| integer_coefficients(term, tolerance))
+            .collect(),
+    )
+}
+
+// the coefficients multiplied with the least common multiple of their denominators
+fn integer_coefficients(term: &Term, tolerance: &Tolerance) -> Vec<BigInt> {
+    let coefficients = (0..=term.degree(tolerance).unwrap_or_default())
+        .map(|exponent| match term.coefficient(exponent) {
+            Scalar::Exact(value) => value,
+            coefficient => unreachable!("the coefficient {coefficient} is not exact"),
+        })
+        .collect::<Vec<BigRational>>();
+    let denominator = coefficients
+        .iter()
+        .fold(BigInt::one(), |lcm, value| lcm.lcm(value.denom()));
+    coefficients
+        .iter()
+        .map(|value| (value * &denominator).to_integer())
+        .collect()
+}
+
+// the sign of the polynomial at n/d, which is the sign of d^degree p(n/d) and evaluated with
+// integers only
+fn sign(coefficients: &[BigInt], value: &BigRational) -> Sign {
+    let mut sum = BigInt::zero();
+    let mut power = BigInt::one();
+    for coefficient in coefficients.iter().rev() {
+        sum = sum * value.numer() + coefficient * &power;
+        power *= value.denom();
+    }
+    sum.sign()
+}
+
+// the number of sign changes of the sequence at the value, where zeroes are skipped
+fn sign_changes(sequence: &[Vec<BigInt>], value: &BigRational) -> usize {
+    let signs = sequence
+        .iter()
+        .map(|coefficients| sign(coefficients, value))
+        .filter(|sign| *sign != Sign::NoSign)
+        .collect::<Vec<Sign>>();
+    signs
+        .windows(2)
+        .filter(|signs| signs[0] != signs[1])
+        .count()
+}
+
+// the exact number of distinct real roots in the half-open interval (lower, upper]
+pub fn count(sequence: &[Vec<BigInt>], lower: &BigRational, upper: &BigRational) -> usize {
+    sign_changes(sequence, lower).saturating_sub(sign_changes(sequence, upper))
+}
+
+// Cauchy's bound 1 + max |aᵢ/aₙ|, which every root is strictly smaller than in magnitude
+fn root_bound(coefficients: &[BigInt]) -> BigRational {
+    let (leading_coefficient, coefficients) = coefficients.split_last().unwrap();
+    let maximum = coefficients
+        .iter()
+        .map(BigInt::abs)
+        .max()
+        .unwrap_or_default();
+    BigRational::new(maximum, leading_coefficient.abs()) + BigRational::one()
+}
+
+// Disjoint half-open intervals (lower, upper] in ascending order which each contain exactly one
+// real root, found by bisecting the interval of Cauchy's bound until every part holds at most one
+pub fn isolate(sequence: &[Vec<BigInt>]) -> Vec<(BigRational, BigRational)> {
+    let bound = root_bound(&sequence[0]);
+    let lower = -&bound;
+    let lower_changes = sign_changes(sequence, &lower);
+    let upper_changes = sign_changes(sequence, &bound);
+    let mut pending = vec![(lower, bound, lower_changes, upper_changes)];
+    let mut intervals = Vec::new();
+    while let Some((lower, upper, lower_changes, upper_changes)) = pending.pop() {
+        match lower_changes.saturating_sub(upper_changes) {
+            0 => {}
+            1 => intervals.push((lower, upper)),
+            _ => {
+                let middle = (&lower + &upper) / BigInt::from(2);
+                let middle_changes = sign_changes(sequence, &middle);
+                pending.push((middle.clone(), upper, middle_changes, upper_changes));
+                pending.push((lower, middle, lower_changes, middle_changes));
+            }
+        }
+    }
+    intervals
+}
+
+// Bisection of an isolating interval until it is narrower than the precision relative to its
+// bounds. The interval collapses to a point if the root is hit exactly
+pub fn refine(
+    sequence: &[Vec<BigInt>],
+    (mut lower, mut upper): (BigRational, BigRational),
+    precision: f64,
+) -> (BigRational, BigRational) {
+    if sign(&sequence[0], &upper) == Sign::NoSign {
+        return (upper.clone(), upper);
+    }
+    let width = |lower: &BigRational, upper: &BigRational| {
+        Scalar::Exact(upper - lower).to_f64()
+            / Scalar::Exact(lower.abs().max(upper.abs()))
+                .to_f64()
+                .max(1.0)
+    };
+    while width(&lower, &upper) > precision {
+        let middle = (&lower + &upper) / BigInt::from(2);
+        if sign(&sequence[0], &middle) == Sign::NoSign {
+            return (middle.clone(), middle);
+        }
+        match count(sequence, &lower, &middle) {
+            0 => lower = middle,
+            _ => upper = middle,
+        }
+    }
+    (lower, upper)
+}
+
+// The rational root n/d in the isolating interval if there is one. The denominator divides the
+// leading coefficient of the polynomial, and the approximation of the root gives the numerator
+// for each of these denominators, which is checked exactly
+pub fn rational_root(
+    sequence: &[Vec<BigInt>],
+    approximation: f64,
+    (lower, upper): (&BigRational, &BigRational),
+) -> Option<BigRational> {
+    let leading_coefficient = sequence[0].last()?;
+    (1..=MAX_DENOMINATOR)
+        .filter(|denominator| (leading_coefficient % denominator).is_zero())
+        .find_map(|denominator| {
+            let numerator = BigRational::from_float(approximation * denominator as f64)?
+                .round()
+                .to_integer();
+            let candidate = BigRational::new(numerator, BigInt::from(denominator));
+            (lower < &candidate
+                && &candidate <= upper
+                && sign(&sequence[0], &candidate) == Sign::NoSign)
+                .then_some(candidate)
+        })
+}
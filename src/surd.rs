@@ -0,0 +1,110 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::locale::Locale;
+
+// trial division for square factors stops here so huge radicands are left partially simplified
+const MAX_SQUARE_FACTOR: u32 = 100_000;
+
+// rational + coefficient * √radicand with a square-free radicand
+#[derive(Clone, Debug, PartialEq)]
+pub struct Surd {
+    pub rational: BigRational,
+    pub coefficient: BigRational,
+    pub radicand: BigInt,
+}
+
+impl Surd {
+    pub fn sqrt(value: &BigRational) -> Self {
+        // √(n/d) = √(nd)/d
+        let mut radicand = value.numer() * value.denom();
+        let mut coefficient = BigRational::new(BigInt::one(), value.denom().clone());
+
+        let mut factor = BigInt::from(2);
+        let mut square = BigInt::from(4);
+        while square <= radicand && factor <= BigInt::from(MAX_SQUARE_FACTOR) {
+            while radicand.is_multiple_of(&square) {
+                radicand /= &square;
+                coefficient *= BigRational::from_integer(factor.clone());
+            }
+            factor += 1;
+            square = &factor * &factor;
+        }
+
+        Self {
+            rational: BigRational::zero(),
+            coefficient,
+            radicand,
+        }
+    }
+
+    pub fn scale(&self, factor: &BigRational) -> Self {
+        Self {
+            rational: &self.rational * factor,
+            coefficient: &self.coefficient * factor,
+            radicand: self.radicand.clone(),
+        }
+    }
+
+    pub fn shift(&self, addend: &BigRational) -> Self {
+        Self {
+            rational: &self.rational + addend,
+            coefficient: self.coefficient.clone(),
+            radicand: self.radicand.clone(),
+        }
+    }
+
+    // If the two parts nearly cancel, the value is computed as (r² - c²n) / (r - c√n) whose
+    // numerator is exact
+    pub fn to_f64(&self) -> f64 {
+        let rational = self.rational.to_f64().unwrap_or(f64::NAN);
+        let root = self.coefficient.to_f64().unwrap_or(f64::NAN)
+            * self.radicand.to_f64().unwrap_or(f64::NAN).sqrt();
+        let sum = rational + root;
+        match sum.abs() < rational.abs() / 2.0 {
+            true => {
+                let numerator = &self.rational * &self.rational
+                    - &self.coefficient
+                        * &self.coefficient
+                        * BigRational::from_integer(self.radicand.clone());
+                numerator.to_f64().unwrap_or(f64::NAN) / (rational - root)
+            }
+            false => sum,
+        }
+    }
+}
+
+impl Surd {
+    pub fn format(&self, locale: Locale) -> String {
+        let denominator = self.rational.denom().lcm(self.coefficient.denom());
+        let rational = (&self.rational * &denominator).to_integer();
+        let coefficient = (&self.coefficient * &denominator).to_integer();
+        let radicand = locale.format_number(&self.radicand);
+
+        let radical = match coefficient.abs().is_one() {
+            true => format!("√{radicand}"),
+            false => format!("{}√{radicand}", locale.format_number(coefficient.abs())),
+        };
+        let numerator = match (rational.is_zero(), coefficient.is_negative()) {
+            (true, true) => format!("-{radical}"),
+            (true, false) => radical,
+            (false, true) => format!("{} - {radical}", locale.format_number(&rational)),
+            (false, false) => format!("{} + {radical}", locale.format_number(&rational)),
+        };
+
+        match (denominator.is_one(), rational.is_zero()) {
+            (true, _) => numerator,
+            (false, true) => format!("{numerator}/{}", locale.format_number(&denominator)),
+            (false, false) => format!("({numerator})/{}", locale.format_number(&denominator)),
+        }
+    }
+}
+
+impl Display for Surd {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.format(Locale::Neutral))
+    }
+}
@@ -0,0 +1,82 @@
+use SystemResult::*;
+
+use crate::locale::Locale;
+use crate::scalar::Scalar;
+
+#[derive(PartialEq, Debug)]
+pub enum SystemResult {
+    Solution(Vec<Scalar>),
+    Inconsistent,
+    SolutionFamily(Vec<ParametrizedValue>),
+}
+
+#[derive(PartialEq, Debug)]
+pub enum ParametrizedValue {
+    Free,
+    // constant + the sum of the coefficients times the free unknowns with the given indices
+    Dependent {
+        constant: Scalar,
+        parameters: Vec<(usize, Scalar)>,
+    },
+}
+
+impl SystemResult {
+    pub fn format(&self, unknowns: &[String], locale: Locale) -> String {
+        match self {
+            Solution(values) => locale.join(
+                &unknowns
+                    .iter()
+                    .zip(values)
+                    .map(|(unknown, value)| format!("{unknown} = {}", value.format(locale)))
+                    .collect::<Vec<String>>(),
+            ),
+            Inconsistent => format!("({}) = {{}}", locale.join(unknowns)),
+            SolutionFamily(values) => locale.join(
+                &unknowns
+                    .iter()
+                    .zip(values)
+                    .map(|(unknown, value)| match value {
+                        ParametrizedValue::Free => format!("{unknown} ∈ R"),
+                        ParametrizedValue::Dependent {
+                            constant,
+                            parameters,
+                        } => format!(
+                            "{unknown} = {}",
+                            format_linear(constant, parameters, unknowns, locale)
+                        ),
+                    })
+                    .collect::<Vec<String>>(),
+            ),
+        }
+    }
+}
+
+fn format_linear(
+    constant: &Scalar,
+    parameters: &[(usize, Scalar)],
+    unknowns: &[String],
+    locale: Locale,
+) -> String {
+    let mut output = match constant.is_zero() && !parameters.is_empty() {
+        true => String::new(),
+        false => constant.format(locale),
+    };
+    for (index, coefficient) in parameters {
+        let sign = match (output.is_empty(), coefficient.is_negative()) {
+            (true, true) => "-",
+            (true, false) => "",
+            (false, true) => " - ",
+            (false, false) => " + ",
+        };
+        let coefficient = coefficient.abs();
+        let coefficient = match coefficient == Scalar::one() {
+            true => String::new(),
+            false if coefficient.is_exact() && coefficient.is_integer() => {
+                coefficient.format(locale)
+            }
+            false => format!("{}·", coefficient.format(locale)),
+        };
+        output.push_str(&format!("{sign}{coefficient}{}", unknowns[*index]));
+    }
+    output
+}
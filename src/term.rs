@@ -1,126 +1,534 @@
+use num_rational::BigRational;
 use ordered_float::OrderedFloat;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::collections::HashSet;
 
+use crate::complex::Complex;
 use crate::equation_element::{
     SymbolType::*,
     ValueType::{self, *},
 };
 use crate::equation_error::EquationError::{self, *};
 use crate::equation_result::EquationResult::{self, *};
-use crate::equation_side::EquationSide;
 use crate::exceptions_in_domain::ExceptionsInDomain::{self, *};
+use crate::expression::Expression;
+use crate::interval::Interval;
+use crate::roots;
+use crate::scalar::Scalar;
+use crate::settings::{RootFinding::*, Settings, Tolerance};
+use crate::sturm;
 
-const MAX_DEGREE: i32 = 2;
+// the highest degree a power of a polynomial is expanded to, which keeps the expansion of powers
+// like (x + 1)^100000 from running out of time and memory
+const MAX_EXPANDED_DEGREE: i32 = 1024;
 
 #[derive(Clone, Debug)]
 pub struct Term {
-    pub addends: HashMap<i32, f64>,
+    pub addends: HashMap<i32, Scalar>,
     pub exceptions_in_domain: ExceptionsInDomain,
+    // divisors depending on symbolic parameters whose zeroes cannot be listed as exceptions
+    pub symbolic_divisors: Vec<Term>,
 }
 
 impl Term {
     pub fn new() -> Self {
         Self {
             addends: HashMap::new(),
-            exceptions_in_domain: Known(HashSet::new()),
+            exceptions_in_domain: Known(HashMap::new()),
+            symbolic_divisors: Vec::new(),
         }
     }
 
     pub fn new_multiplier() -> Self {
         Self {
-            addends: HashMap::from([(0, 1.0)]),
-            exceptions_in_domain: Known(HashSet::new()),
+            addends: HashMap::from([(0, Scalar::one())]),
+            exceptions_in_domain: Known(HashMap::new()),
+            symbolic_divisors: Vec::new(),
         }
     }
 
-    pub fn zeroes(&self) -> Result<EquationResult, EquationError> {
-        let lowest_exponent = self.lowest_exponent();
+    pub fn from_value(value: &ValueType) -> Self {
+        let mut term = Self::new_multiplier();
+        term.multiply_value(value);
+        term
+    }
+
+    pub fn zeroes(&self, settings: &Settings) -> Result<EquationResult, EquationError> {
+        let lowest_exponent = self.lowest_exponent(&settings.tolerance);
         let factorized_variable = lowest_exponent > 0;
         let mut normalized_term = self.clone();
         normalized_term.increase_exponents(-lowest_exponent);
+        // the zeroes of the unresolved divisors are excluded anyway, so the factors the term has
+        // in common with them need not be solved
+        for divisor in self.exceptions_in_domain.divisors() {
+            while let Some(1..) = normalized_term.degree(&settings.tolerance) {
+                let common_factor = normalized_term.gcd(divisor, &settings.tolerance);
+                if common_factor
+                    .degree(&settings.tolerance)
+                    .unwrap_or_default()
+                    == 0
+                {
+                    break;
+                }
+                normalized_term = normalized_term
+                    .divide_polynomial(&common_factor, &settings.tolerance)
+                    .0;
+            }
+        }
 
-        let degree = normalized_term.degree();
-        let degree = match degree {
+        let factors = match normalized_term.degree(&settings.tolerance) {
             None => {
-                let exceptions = self
-                    .exceptions_in_domain
-                    .unwrap_or(|degree| TooHighDegree {
-                        degree,
-                        max_degree: MAX_DEGREE,
-                    })?;
-                let mut exceptions = exceptions.iter().collect::<Vec<&OrderedFloat<f64>>>();
+                let exceptions =
+                    self.exceptions_in_domain
+                        .unwrap_or(&settings.tolerance, |degree| TooHighDegree {
+                            degree,
+                            max_degree: settings.degree_limit(),
+                        })?;
+                let mut exceptions = exceptions.keys().collect::<Vec<&OrderedFloat<f64>>>();
 
                 exceptions.sort();
-                let exceptions = exceptions
+                let mut exceptions = exceptions
                     .iter()
                     .rev()
                     .map(|value| value.into_inner())
-                    .collect();
+                    .collect::<Vec<f64>>();
+                exceptions.dedup_by(|value, other| {
+                    settings.tolerance.approximately_equal(*value, *other)
+                });
                 return Ok(InfiniteSolutions { exceptions });
             }
+            Some(_) if self.is_symbolic() => {
+                return self.symbolic_zeroes(&normalized_term, factorized_variable, settings)
+            }
             Some(0) => {
                 return Ok(
-                    match factorized_variable && self.exceptions_in_domain.zero_is_valid() {
-                        true => Solutions(vec![0.0]),
+                    match factorized_variable
+                        && self.exceptions_in_domain.zero_is_valid(&settings.tolerance)
+                    {
+                        true => Solutions {
+                            solutions: vec![Scalar::zero()],
+                            multiplicities: vec![lowest_exponent as usize],
+                        },
                         false => Unsolvable,
                     },
                 )
             }
-            Some(degree) => degree,
+            Some(_) => normalized_term.square_free_factors(&settings.tolerance),
         };
 
-        if degree > MAX_DEGREE {
+        // the square-free factors have simple roots and often a lower degree
+        let degree = factors
+            .iter()
+            .map(|(factor, _)| factor.degree(&settings.tolerance).unwrap_or_default())
+            .max()
+            .unwrap_or_default();
+        if degree > settings.degree_limit() {
             return Err(TooHighDegree {
                 degree,
-                max_degree: MAX_DEGREE,
+                max_degree: settings.degree_limit(),
             });
         }
 
-        let exceptions_in_domain = self
-            .exceptions_in_domain
-            .unwrap_or(|degree| TooHighDegree {
-                degree,
-                max_degree: MAX_DEGREE,
-            })?;
+        // the error of roots of approximate polynomials is estimated on the whole polynomial,
+        // since the rounding errors of its coefficients also move the roots of its factors
+        let polynomial_coefficients = normalized_term.coefficients(&settings.tolerance);
+        let mut roots = Vec::new();
+        let mut complex_roots = Vec::new();
+        for (factor, multiplicity) in factors {
+            let (real_roots, mut factor_complex_roots) = factor.split_roots(settings)?;
+            let coefficients = factor.coefficients(&settings.tolerance);
+            let error_coefficients =
+                match normalized_term.addends.values().any(Scalar::is_approximate) {
+                    true => &polynomial_coefficients,
+                    false => &coefficients,
+                };
+            // the real roots of square-free factors with exact coefficients are simple and told
+            // apart by their isolating intervals, however close they are
+            let grouped_roots = match factor.addends.values().all(Scalar::is_exact) {
+                true => real_roots.into_iter().map(|root| (root, 1)).collect(),
+                false => roots::group(real_roots, &mut factor_complex_roots, &coefficients),
+            };
+            for (root, root_multiplicity) in grouped_roots {
+                let error = match &root {
+                    Scalar::Approximate(value) => roots::error_bound(error_coefficients, *value),
+                    _ => 0.0,
+                };
+                roots.push((root, root_multiplicity * multiplicity, error));
+            }
+            complex_roots.append(&mut factor_complex_roots);
+        }
+        let zero_is_a_solution =
+            factorized_variable && self.exceptions_in_domain.zero_is_valid(&settings.tolerance);
+        if roots.is_empty() && !zero_is_a_solution && !settings.complex_solutions {
+            return Err(ComplexNumbers);
+        }
 
-        let mut solutions = Vec::new();
-        if factorized_variable && !exceptions_in_domain.contains(&OrderedFloat(0.0)) {
-            solutions.push(0.0);
+        roots.retain(|(value, _, _)| {
+            !self
+                .exceptions_in_domain
+                .excludes(value.to_f64(), &settings.tolerance)
+                && self
+                    .exceptions_in_domain
+                    .is_in_domain(value, &settings.tolerance)
+        });
+        roots.sort_by(|(value, _, _), (other, _, _)| other.to_f64().total_cmp(&value.to_f64()));
+        if zero_is_a_solution {
+            roots.insert(0, (Scalar::zero(), lowest_exponent as usize, 0.0));
         }
+        let solutions = roots
+            .iter()
+            .map(|(value, _, _)| value.clone())
+            .collect::<Vec<Scalar>>();
+        let multiplicities = roots
+            .iter()
+            .map(|(_, multiplicity, _)| *multiplicity)
+            .collect();
 
-        let mut roots = normalized_term
-            .roots()?
+        if !settings.complex_solutions || complex_roots.is_empty() {
+            if settings.error_bounds && solutions.iter().any(Scalar::is_approximate) {
+                return Ok(BoundedSolutions {
+                    solutions,
+                    multiplicities,
+                    errors: roots.iter().map(|(_, _, error)| *error).collect(),
+                });
+            }
+            return Ok(Solutions {
+                solutions,
+                multiplicities,
+            });
+        }
+
+        complex_roots.sort_by(|root, other| other.real.total_cmp(&root.real));
+        let mut solutions = solutions
             .iter()
-            .map(|value| OrderedFloat(*value))
-            .filter(|value| !exceptions_in_domain.contains(value))
-            .collect::<Vec<OrderedFloat<f64>>>();
+            .map(|value| Complex::from(value.to_f64()))
+            .collect::<Vec<Complex>>();
+        for root in complex_roots {
+            solutions.push(root);
+            solutions.push(root.conjugate());
+        }
+        Ok(ComplexSolutions(solutions))
+    }
 
-        roots.sort();
+    // linear equations in the variable whose coefficients depend on symbolic parameters. The
+    // solutions are only valid if the returned conditions are nonzero
+    fn symbolic_zeroes(
+        &self,
+        normalized_term: &Term,
+        factorized_variable: bool,
+        settings: &Settings,
+    ) -> Result<EquationResult, EquationError> {
+        let mut solutions = match normalized_term.degree(&settings.tolerance) {
+            Some(0) => Vec::new(),
+            Some(1) => vec![-&(&normalized_term.coefficient(0) / &normalized_term.coefficient(1))],
+            _ => return Err(SymbolicParameters),
+        };
+        if factorized_variable {
+            solutions.push(Scalar::zero());
+        }
 
-        let mut roots = roots.iter().rev().map(|value| value.into_inner()).collect();
+        let mut conditions = vec![normalized_term.coefficient(
+            normalized_term
+                .degree(&settings.tolerance)
+                .unwrap_or_default(),
+        )];
+        let mut valid_solutions = Vec::new();
+        for solution in solutions {
+            let divisor_values = self
+                .symbolic_divisors
+                .iter()
+                .chain(self.exceptions_in_domain.divisors())
+                .map(|divisor| divisor.substitute(&solution))
+                .chain(
+                    self.exceptions_in_domain
+                        .exceptions()
+                        .values()
+                        .map(|exception| &solution - exception),
+                )
+                .collect::<Vec<Scalar>>();
+            if divisor_values.iter().any(Scalar::is_zero) {
+                continue;
+            }
+            conditions.extend(divisor_values);
+            valid_solutions.push(solution);
+        }
 
-        solutions.append(&mut roots);
-        Ok(Solutions(solutions))
+        if valid_solutions.is_empty() {
+            return Ok(Unsolvable);
+        }
+        let mut nonzero_factors = Vec::new();
+        for condition in conditions {
+            if let Scalar::Symbolic(expression) = condition {
+                for factor in expression.nonzero_factors() {
+                    let factor = Scalar::from(factor);
+                    if !nonzero_factors.contains(&factor) {
+                        nonzero_factors.push(factor);
+                    }
+                }
+            }
+        }
+        Ok(match nonzero_factors.is_empty() {
+            true => EquationResult::simple_solutions(valid_solutions),
+            false => ConditionalSolutions {
+                solutions: valid_solutions,
+                conditions: nonzero_factors,
+            },
+        })
     }
 
-    fn roots(&self) -> Result<Vec<f64>, EquationError> {
-        let a = *self.addends.get(&2).unwrap_or(&0.0);
-        let b = *self.addends.get(&1).unwrap_or(&0.0);
-        let c = *self.addends.get(&0).unwrap_or(&0.0);
+    // the real zeroes including the ones which are exceptions in the domain
+    pub fn real_zeroes(&self, settings: &Settings) -> Result<Vec<Scalar>, EquationError> {
+        let lowest_exponent = self.lowest_exponent(&settings.tolerance);
+        let mut normalized_term = self.clone();
+        normalized_term.increase_exponents(-lowest_exponent);
 
-        if a == 0.0 {
-            return Ok(vec![-c / b]);
+        let mut zeroes = Vec::new();
+        if let Some(1..) = normalized_term.degree(&settings.tolerance) {
+            for (factor, _) in normalized_term.square_free_factors(&settings.tolerance) {
+                match factor.degree(&settings.tolerance).unwrap_or_default() {
+                    degree if degree > settings.degree_limit() => {
+                        return Err(TooHighDegree {
+                            degree,
+                            max_degree: settings.degree_limit(),
+                        })
+                    }
+                    _ => zeroes.append(&mut factor.split_roots(settings)?.0),
+                }
+            }
         }
+        if lowest_exponent > 0 {
+            zeroes.push(Scalar::zero());
+        }
+        Ok(zeroes)
+    }
 
-        if b * b - 4.0 * a * c < 0.0 {
-            return Err(ComplexNumbers);
+    // the real roots and the complex roots with a positive imaginary part
+    fn split_roots(
+        &self,
+        settings: &Settings,
+    ) -> Result<(Vec<Scalar>, Vec<Complex>), EquationError> {
+        if let Some(roots) = self.exact_roots(&settings.tolerance) {
+            return Ok((roots, Vec::new()));
+        }
+
+        let roots = self.roots(settings);
+        let sequence = match sturm::sequence(self, &settings.tolerance) {
+            Some(sequence) => sequence,
+            None => {
+                let roots = roots?;
+                return Ok((
+                    roots
+                        .iter()
+                        .filter(|root| root.is_real())
+                        .map(|root| Scalar::Approximate(root.real))
+                        .collect(),
+                    roots
+                        .into_iter()
+                        .filter(|root| root.imaginary > 0.0)
+                        .collect(),
+                ));
+            }
+        };
+
+        // The isolating intervals decide how many real roots there are. A numeric root in an
+        // interval is kept, while roots the iteration missed or took for a complex pair are found
+        // by refining the interval, so none is lost if the iteration fails to converge
+        let (numeric_roots, mut complex_roots) = match roots {
+            Ok(roots) => (
+                roots
+                    .iter()
+                    .filter(|root| root.is_real())
+                    .map(|root| root.real)
+                    .collect(),
+                roots
+                    .into_iter()
+                    .filter(|root| root.imaginary > 0.0)
+                    .collect(),
+            ),
+            Err(error) if settings.complex_solutions => return Err(error),
+            Err(_) => (Vec::new(), Vec::new()),
+        };
+        let coefficients = self.coefficients(&settings.tolerance);
+        let mut real_roots = Vec::new();
+        for (lower, upper) in sturm::isolate(&sequence).into_iter().rev() {
+            // the numeric root is kept if the interval of the precision around it holds the root
+            let certified_root = numeric_roots.iter().copied().find(|root| {
+                let radius = settings.precision * root.abs().max(1.0) / 2.0;
+                let certified_lower = BigRational::from_float(root - radius).unwrap_or_default();
+                let certified_upper = BigRational::from_float(root + radius).unwrap_or_default();
+                BigRational::from_float(*root).is_some_and(|root| lower < root && root <= upper)
+                    && sturm::count(
+                        &sequence,
+                        &certified_lower.max(lower.clone()),
+                        &certified_upper.min(upper.clone()),
+                    ) == 1
+            });
+            let root = match certified_root {
+                Some(root) => root,
+                None => {
+                    let (lower, upper) = sturm::refine(
+                        &sequence,
+                        (lower.clone(), upper.clone()),
+                        settings.precision,
+                    );
+                    if lower == upper {
+                        real_roots.push(Scalar::Exact(lower));
+                        continue;
+                    }
+                    let (lower, upper) =
+                        (Scalar::Exact(lower).to_f64(), Scalar::Exact(upper).to_f64());
+                    roots::polish(&coefficients, (lower + upper) / 2.0).clamp(lower, upper)
+                }
+            };
+            // a rational root is recovered exactly from its approximation
+            real_roots.push(
+                match sturm::rational_root(&sequence, root, (&lower, &upper)) {
+                    Some(root) => Scalar::Exact(root),
+                    None => Scalar::Approximate(root),
+                },
+            );
+        }
+        let degree = self.degree(&settings.tolerance).unwrap_or_default() as usize;
+        while !complex_roots.is_empty() && 2 * complex_roots.len() + real_roots.len() > degree {
+            let closest_to_real_axis = (0..complex_roots.len())
+                .min_by(|index, other| {
+                    complex_roots[*index]
+                        .imaginary
+                        .total_cmp(&complex_roots[*other].imaginary)
+                })
+                .unwrap();
+            complex_roots.remove(closest_to_real_axis);
+        }
+        Ok((real_roots, complex_roots))
+    }
+
+    fn exact_roots(&self, tolerance: &Tolerance) -> Option<Vec<Scalar>> {
+        let coefficients = (0..=self.degree(tolerance)?)
+            .map(|exponent| self.coefficient(exponent))
+            .collect::<Vec<Scalar>>();
+        roots::exact(&coefficients)
+    }
+
+    // closed-form roots are polished with Newton steps on the polynomial, which recovers digits
+    // lost in the formulas
+    fn roots(&self, settings: &Settings) -> Result<Vec<Complex>, EquationError> {
+        let coefficients = self.coefficients(&settings.tolerance);
+        let roots = match (&coefficients[..], settings.root_finding) {
+            ([b, a], _) => roots::linear(*a, *b),
+            ([c, b, a], _) => roots::quadratic(*a, *b, *c),
+            ([d, c, b, a], Automatic | ClosedForm) => roots::cubic(*a, *b, *c, *d),
+            ([e, d, c, b, a], Automatic | ClosedForm) => roots::quartic(*a, *b, *c, *d, *e),
+            _ => return roots::polynomial(&coefficients, settings.precision).ok_or(NoConvergence),
+        };
+        Ok(roots
+            .into_iter()
+            .map(|root| match root.is_real() {
+                true => Complex::from(roots::polish(&coefficients, root.real)),
+                false => root,
+            })
+            .collect())
+    }
+
+    // the coefficients of the polynomial ordered by exponent
+    pub fn coefficients(&self, tolerance: &Tolerance) -> Vec<f64> {
+        (0..=self.degree(tolerance).unwrap_or_default())
+            .map(|exponent| self.coefficient(exponent).to_f64())
+            .collect()
+    }
+
+    pub fn substitute(&self, value: &Scalar) -> Scalar {
+        let mut sum = Scalar::zero();
+        for (exponent, coefficient) in self.addends.iter() {
+            let power = value
+                .pow(&Scalar::from(*exponent))
+                .unwrap_or(Scalar::Approximate(f64::NAN));
+            sum += &(coefficient * &power);
+        }
+        sum
+    }
+
+    pub fn evaluate(&self, value: f64) -> f64 {
+        self.addends
+            .iter()
+            .map(|(exponent, coefficient)| coefficient.to_f64() * value.powi(*exponent))
+            .sum()
+    }
+
+    // the value with the symbolic parameters substituted and the sum of the absolute values of
+    // the addends, which decides how close to zero a value can be told apart from zero
+    pub fn evaluate_parameters(&self, value: f64, parameters: &HashMap<String, f64>) -> (f64, f64) {
+        let mut sum = 0.0;
+        let mut magnitude = 0.0;
+        for (exponent, coefficient) in self.addends.iter() {
+            let (coefficient, coefficient_magnitude) = match coefficient {
+                Scalar::Symbolic(expression) => (
+                    expression.evaluate(parameters),
+                    expression.magnitude(parameters),
+                ),
+                coefficient => (coefficient.to_f64(), coefficient.to_f64().abs()),
+            };
+            let power = value.powi(*exponent);
+            sum += coefficient * power;
+            magnitude += coefficient_magnitude * power.abs();
+        }
+        (sum, magnitude)
+    }
+
+    // the term multiplied with the denominators of its symbolic coefficients
+    pub fn clear_denominators(&self) -> Term {
+        let mut denominators: Vec<Expression> = Vec::new();
+        for coefficient in self.addends.values() {
+            if let Scalar::Symbolic(expression) = coefficient {
+                let denominator = expression.denominator();
+                if denominator.as_constant().is_none() && !denominators.contains(&denominator) {
+                    denominators.push(denominator);
+                }
+            }
+        }
+
+        let mut cleared = Term::new();
+        for (exponent, coefficient) in self.addends.iter() {
+            let expression = Expression::from(coefficient);
+            let own_denominator = expression.denominator();
+            let mut product = expression.numerator();
+            for denominator in denominators.iter() {
+                if *denominator != own_denominator {
+                    product = &product * denominator;
+                }
+            }
+            cleared.addends.insert(*exponent, Scalar::from(product));
         }
+        cleared
+    }
 
-        Ok(vec![
-            (-b + (b * b - 4.0 * a * c).sqrt()) / (2.0 * a),
-            (-b - (b * b - 4.0 * a * c).sqrt()) / (2.0 * a),
-        ])
+    // the terms which are multiplied with the powers of the symbolic parameter
+    pub fn coefficients_of(&self, parameter: &str) -> BTreeMap<i32, Term> {
+        let mut coefficients: BTreeMap<i32, Term> = BTreeMap::new();
+        for (exponent, coefficient) in self.addends.iter() {
+            let parts = match coefficient {
+                Scalar::Symbolic(expression) => expression
+                    .coefficients_of(parameter)
+                    .into_iter()
+                    .map(|(power, part)| (power, Scalar::from(part)))
+                    .collect(),
+                coefficient => vec![(0, coefficient.clone())],
+            };
+            for (power, part) in parts {
+                *coefficients
+                    .entry(power)
+                    .or_insert_with(Term::new)
+                    .addends
+                    .entry(*exponent)
+                    .or_insert_with(Scalar::zero) += &part;
+            }
+        }
+        coefficients
+    }
+
+    pub fn add_term(&mut self, other: &Term) {
+        for (exponent, coefficient) in other.addends.iter() {
+            *self.addends.entry(*exponent).or_insert_with(Scalar::zero) += coefficient;
+        }
     }
 
     pub fn multiply_value(&mut self, value: &ValueType) {
@@ -133,28 +541,14 @@ impl Term {
         }
     }
 
-    pub fn divide_value(&mut self, value: &ValueType) -> Result<(), EquationError> {
-        match value {
-            Number(constant) => self.divide_constant(constant)?,
-            Symbol(symbol) => match symbol {
-                Variable => {
-                    self.increase_exponents(-1);
-                    self.exceptions_in_domain.insert_zero();
-                }
-                Constant(constant) => self.divide_constant(constant)?,
-            },
-        }
-        Ok(())
-    }
-
-    pub fn multiply_constant(&mut self, constant: &f64) {
+    pub fn multiply_constant(&mut self, constant: &Scalar) {
         for (_, coefficient) in self.addends.iter_mut() {
             *coefficient *= constant;
         }
     }
 
-    pub fn divide_constant(&mut self, constant: &f64) -> Result<(), EquationError> {
-        if *constant == 0.0 {
+    pub fn divide_constant(&mut self, constant: &Scalar) -> Result<(), EquationError> {
+        if constant.is_zero() {
             return Err(DivisionByZero);
         }
 
@@ -168,89 +562,280 @@ impl Term {
         let new_addends = self
             .addends
             .iter()
-            .map(|(exponent, coefficient)| (exponent + power, *coefficient))
+            .map(|(exponent, coefficient)| (exponent + power, coefficient.clone()))
             .collect();
 
         self.addends = new_addends;
     }
 
+    pub fn raise(&mut self, exponent: &Scalar) -> Result<(), EquationError> {
+        if exponent.is_symbolic() {
+            return Err(InvalidExponent);
+        }
+        if let Some(constant) = self.constant() {
+            let power = constant.pow(exponent).ok_or(
+                match (constant.is_symbolic(), exponent.is_integer()) {
+                    (true, true) => InvalidExponent,
+                    (true, false) => RootOfSymbolicParameter,
+                    (false, _) => ComplexNumbers,
+                },
+            )?;
+            self.addends = HashMap::from([(0, power)]);
+            return Ok(());
+        }
+        if !exponent.is_integer() {
+            return Err(InvalidExponent);
+        }
+
+        let exponent = exponent.to_f64();
+        let degree = self.addends.keys().map(|exponent| exponent.abs()).max();
+        let expanded_degree = degree.unwrap_or_default() as f64 * exponent;
+        if expanded_degree > MAX_EXPANDED_DEGREE as f64 {
+            return Err(TooHighDegree {
+                degree: expanded_degree.min(i32::MAX as f64) as i32,
+                max_degree: MAX_EXPANDED_DEGREE,
+            });
+        }
+
+        // exponentiation by squaring
+        let mut exponent = exponent as i32;
+        let mut base = self.clone();
+        let mut power = Term::new_multiplier();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                power.multiply_term(&base);
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                let square = base.clone();
+                base.multiply_term(&square);
+            }
+        }
+        self.addends = power.addends;
+        Ok(())
+    }
+
     pub fn multiply_term(&mut self, other: &Term) {
         let mut addends = HashMap::new();
         for (exponent, coefficient) in self.addends.iter() {
             for (other_exponent, other_coefficient) in other.addends.iter() {
-                *addends.entry(exponent + other_exponent).or_insert(0.0) +=
-                    coefficient * other_coefficient;
+                *addends
+                    .entry(exponent + other_exponent)
+                    .or_insert_with(Scalar::zero) += &(coefficient * other_coefficient);
             }
         }
 
         self.addends = addends;
     }
 
-    pub fn divide_term(
-        &mut self,
-        other: &Term,
-        nested_term: &mut Term,
-        equation_side_multiplier: &mut Term,
-        other_equation_side: &mut EquationSide,
-    ) -> Result<(), EquationError> {
-        self.add_exceptions_in_domain_of_divisor(other)?;
-        let addends = other
+    // Cancels the common factor of the term and a denominator. A power of the variable in the
+    // denominator moves into the exponents of the term and a constant denominator into its
+    // coefficients, so the denominator is either 1 or a polynomial with a nonzero constant
+    pub fn reduce_fraction(&mut self, denominator: &mut Term, tolerance: &Tolerance) {
+        let lowest_exponent = denominator.lowest_exponent(tolerance);
+        self.increase_exponents(-lowest_exponent);
+        *denominator = denominator.polynomial();
+        denominator.increase_exponents(-lowest_exponent);
+
+        if denominator.degree(tolerance).unwrap_or_default() > 0 {
+            let shift = self.lowest_exponent(tolerance);
+            let mut numerator = self.polynomial();
+            numerator.increase_exponents(-shift);
+            let common_factor = numerator.gcd(denominator, tolerance);
+            if common_factor.degree(tolerance).unwrap_or_default() > 0 {
+                let (mut quotient, _) = numerator.divide_polynomial(&common_factor, tolerance);
+                quotient.increase_exponents(shift);
+                self.addends = quotient.addends;
+                *denominator = denominator.divide_polynomial(&common_factor, tolerance).0;
+            }
+        }
+
+        if denominator.degree(tolerance) == Some(0) {
+            self.divide_constant(&denominator.coefficient(0)).unwrap();
+            *denominator = Term::new_multiplier();
+        }
+    }
+
+    // the addends without the exceptions in the domain
+    fn polynomial(&self) -> Term {
+        let mut polynomial = Term::new();
+        polynomial.addends = self
             .addends
             .iter()
-            .filter(|(_, coefficient)| **coefficient != 0.0)
-            .collect::<Vec<(&i32, &f64)>>();
-        if addends.len() == 1 {
-            let (exponent, coefficient) = addends[0];
-            self.increase_exponents(-exponent);
-            self.divide_constant(coefficient).unwrap();
-            return Ok(());
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(exponent, coefficient)| (*exponent, coefficient.clone()))
+            .collect();
+        polynomial
+    }
+
+    pub fn derivative(&self) -> Term {
+        let mut derivative = Term::new();
+        derivative.addends = self
+            .addends
+            .iter()
+            .filter(|(exponent, coefficient)| **exponent != 0 && !coefficient.is_zero())
+            .map(|(exponent, coefficient)| (exponent - 1, coefficient * &Scalar::from(*exponent)))
+            .collect();
+        derivative
+    }
+
+    // Yun's algorithm splitting a polynomial into coprime square-free factors, each with the
+    // multiplicity its roots have in the polynomial
+    pub fn square_free_factors(&self, tolerance: &Tolerance) -> Vec<(Term, usize)> {
+        let polynomial = self.polynomial();
+        let mut repeated = polynomial.gcd(&polynomial.derivative(), tolerance);
+        if repeated.degree(tolerance).unwrap_or_default() == 0 {
+            return vec![(polynomial, 1)];
         }
-        nested_term.multiply_term(other);
-        equation_side_multiplier.multiply_term(other);
-        other_equation_side.multiplier.multiply_term(other);
-        Ok(())
+
+        // the product of the distinct factors which have at least the current multiplicity
+        let mut remaining = polynomial.divide_polynomial(&repeated, tolerance).0;
+        let mut factors = Vec::new();
+        let mut multiplicity = 1;
+        while remaining.degree(tolerance).unwrap_or_default() > 0 {
+            let next_remaining = remaining.gcd(&repeated, tolerance);
+            let factor = remaining.divide_polynomial(&next_remaining, tolerance).0;
+            if factor.degree(tolerance).unwrap_or_default() > 0 {
+                factors.push((factor, multiplicity));
+            }
+            repeated = repeated.divide_polynomial(&next_remaining, tolerance).0;
+            remaining = next_remaining;
+            multiplicity += 1;
+        }
+        factors
+    }
+
+    // Long division by a nonzero polynomial, returning the quotient and the remainder. Approximate
+    // coefficients of the remainder which are negligible compared to the dividend are rounding
+    // errors and dropped
+    pub fn divide_polynomial(&self, divisor: &Term, tolerance: &Tolerance) -> (Term, Term) {
+        let divisor_degree = divisor.degree(tolerance).unwrap_or_default();
+        let leading_coefficient = divisor.coefficient(divisor_degree);
+        let magnitude = self
+            .addends
+            .values()
+            .map(|coefficient| coefficient.to_f64().abs())
+            .fold(0.0, f64::max);
+        let mut quotient = Term::new();
+        let mut remainder = self.polynomial();
+        while let Some(degree) = remainder
+            .degree(tolerance)
+            .filter(|degree| *degree >= divisor_degree)
+        {
+            let factor = &remainder.coefficient(degree) / &leading_coefficient;
+            for (exponent, coefficient) in divisor.addends.iter() {
+                *remainder
+                    .addends
+                    .entry(exponent + degree - divisor_degree)
+                    .or_insert_with(Scalar::zero) -= &(&factor * coefficient);
+            }
+            remainder.addends.remove(&degree);
+            remainder
+                .addends
+                .retain(|_, coefficient| match coefficient {
+                    Scalar::Approximate(value) => !tolerance.is_negligible(*value, magnitude),
+                    coefficient => !coefficient.is_zero(),
+                });
+            quotient.addends.insert(degree - divisor_degree, factor);
+        }
+        (quotient, remainder)
+    }
+
+    // The monic greatest common divisor of two polynomials by the Euclidean algorithm. Polynomials
+    // with symbolic coefficients are treated as coprime
+    pub fn gcd(&self, other: &Term, tolerance: &Tolerance) -> Term {
+        if self.is_symbolic() || other.is_symbolic() {
+            return Term::new_multiplier();
+        }
+        let mut divisor = self.polynomial();
+        let mut remainder = other.polynomial();
+        while remainder.degree(tolerance).is_some() {
+            let next_remainder = divisor.divide_polynomial(&remainder, tolerance).1;
+            divisor = remainder;
+            remainder = next_remainder;
+        }
+        if let Some(degree) = divisor.degree(tolerance) {
+            let leading_coefficient = divisor.coefficient(degree);
+            divisor.divide_constant(&leading_coefficient).unwrap();
+        }
+        divisor
     }
 
     pub fn add_exceptions_in_domain_of_divisor(
         &mut self,
         divisor: &Term,
+        settings: &Settings,
     ) -> Result<(), EquationError> {
-        match &mut self.exceptions_in_domain {
-            Known(exceptions) => {
-                let exceptions_in_domain = match divisor.zeroes() {
-                    Ok(Solutions(values)) => values.into_iter().map(OrderedFloat).collect(),
-                    Ok(Unsolvable) => HashSet::new(),
-                    Ok(InfiniteSolutions { .. }) => return Err(DivisionByZero),
-                    Err(TooHighDegree { degree, .. }) => {
-                        let zero_is_valid = !exceptions.contains(&OrderedFloat(0.0))
-                            && !divisor.zero_is_a_solution();
-                        self.exceptions_in_domain = Unknown {
-                            zero_is_valid,
-                            degree,
-                        };
-                        return Ok(());
-                    }
-                    Err(error) => return Err(error),
-                };
+        if divisor.is_symbolic() {
+            self.symbolic_divisors.push(divisor.clone());
+            return Ok(());
+        }
 
-                exceptions.extend(exceptions_in_domain.iter());
-            }
-            Unknown { zero_is_valid, .. } => {
-                if !divisor.zero_is_a_solution() {
-                    *zero_is_valid = false;
+        let exceptions = match divisor.zeroes(settings) {
+            Ok(
+                Solutions {
+                    solutions: values, ..
                 }
+                | BoundedSolutions {
+                    solutions: values, ..
+                },
+            ) => values
+                .iter()
+                .map(|value| (OrderedFloat(value.to_f64()), value.clone()))
+                .collect(),
+            Ok(ComplexSolutions(values)) => values
+                .into_iter()
+                .filter(Complex::is_real)
+                .map(|value| (OrderedFloat(value.real), Scalar::Approximate(value.real)))
+                .collect(),
+            Ok(Unsolvable) | Err(ComplexNumbers) => HashMap::new(),
+            Ok(InfiniteSolutions { .. }) => return Err(DivisionByZero),
+            Ok(
+                Intervals(_)
+                | ConditionalSolutions { .. }
+                | FilteredSolutions { .. }
+                | PeriodicSolutions { .. }
+                | NumericSolutions { .. },
+            ) => {
+                unreachable!("zeroes of numeric divisors are neither intervals nor conditional")
             }
-        }
+            // the divisor is kept to be evaluated at the solutions instead
+            Err(TooHighDegree { .. }) => {
+                self.exceptions_in_domain.extend(
+                    &Unresolved {
+                        exceptions: HashMap::new(),
+                        divisors: vec![divisor.clone()],
+                        domain: vec![Interval::real_line()],
+                    },
+                    &settings.tolerance,
+                );
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        };
+        self.exceptions_in_domain
+            .extend(&Known(exceptions), &settings.tolerance);
         Ok(())
     }
 
-    pub fn degree(&self) -> Option<i32> {
+    pub fn extend_exceptions(&mut self, other: &Term, tolerance: &Tolerance) {
+        self.exceptions_in_domain
+            .extend(&other.exceptions_in_domain, tolerance);
+        self.symbolic_divisors
+            .extend(other.symbolic_divisors.iter().cloned());
+    }
+
+    pub fn is_symbolic(&self) -> bool {
+        self.addends.values().any(Scalar::is_symbolic)
+    }
+
+    pub fn degree(&self, tolerance: &Tolerance) -> Option<i32> {
         let mut degree = None;
         for (exponent, coefficient) in self.addends.iter() {
             if match degree {
                 Some(lowest_degree) => *exponent > lowest_degree,
                 None => true,
-            } && *coefficient != 0.0
+            } && self.is_present(coefficient, tolerance)
             {
                 degree = Some(*exponent);
             }
@@ -258,13 +843,13 @@ impl Term {
         degree
     }
 
-    pub fn lowest_exponent(&self) -> i32 {
+    pub fn lowest_exponent(&self, tolerance: &Tolerance) -> i32 {
         let mut lowest_degree = None;
         for (exponent, coefficient) in self.addends.iter() {
             if match lowest_degree {
                 Some(lowest_degree) => *exponent < lowest_degree,
                 None => true,
-            } && *coefficient != 0.0
+            } && self.is_present(coefficient, tolerance)
             {
                 lowest_degree = Some(*exponent);
             }
@@ -272,8 +857,39 @@ impl Term {
         lowest_degree.unwrap_or_default()
     }
 
-    pub fn zero_is_a_solution(&self) -> bool {
-        self.lowest_exponent() > 0
+    // approximate coefficients which are negligible compared to the largest coefficient are
+    // rounding errors of cancelling values like 0.1 + 0.2 - 0.3
+    fn is_present(&self, coefficient: &Scalar, tolerance: &Tolerance) -> bool {
+        match coefficient {
+            Scalar::Approximate(value) => {
+                let magnitude = self
+                    .addends
+                    .values()
+                    .filter(|coefficient| !coefficient.is_symbolic())
+                    .map(|coefficient| coefficient.to_f64().abs())
+                    .fold(0.0, f64::max);
+                !tolerance.is_negligible(*value, magnitude)
+            }
+            coefficient => !coefficient.is_zero(),
+        }
+    }
+
+    pub fn constant(&self) -> Option<Scalar> {
+        match self
+            .addends
+            .iter()
+            .all(|(exponent, coefficient)| *exponent == 0 || coefficient.is_zero())
+        {
+            true => Some(self.coefficient(0)),
+            false => None,
+        }
+    }
+
+    pub fn coefficient(&self, exponent: i32) -> Scalar {
+        self.addends
+            .get(&exponent)
+            .cloned()
+            .unwrap_or_else(Scalar::zero)
     }
 }
 
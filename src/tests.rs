@@ -1,29 +1,62 @@
-use crate::equation_error::EquationError::*;
-use crate::equation_result::EquationResult::*;
+// the test macros collect the expected values by pushing them one by one
+#![allow(clippy::vec_init_then_push)]
+
+use num_rational::BigRational;
+use std::collections::HashMap;
+
+use crate::complex::Complex;
+use crate::equation::Equation;
+use crate::equation_error::{Diagnostic, EquationError::*};
+use crate::equation_result::EquationResult::{self, *};
+use crate::interval::Bound::*;
+use crate::locale::Locale;
+use crate::scalar::{self, Scalar};
+use crate::settings::{AngleUnit, RootFinding::*, Settings, Tolerance};
 use crate::solve_equation;
+use crate::solve_system;
+use crate::sturm;
+use crate::term::Term;
 use crate::token_stream::EquationInput;
+use crate::verification::{self, OriginalEquation, Rejection};
 
 const TOLERANCE: f64 = 0.001;
 
+fn solve_input(input: &EquationInput) -> Result<EquationResult, Diagnostic> {
+    solve_equation::solve_equation(input).map(|(result, _)| result)
+}
+
 macro_rules! test_solutions {
     ($equation:expr) => {
         let input = EquationInput::new(String::from($equation), String::from("x"));
-        let equation_result = solve_equation::solve_equation(&input);
+        let equation_result = solve_input(&input);
         assert_eq!(equation_result, Ok(Unsolvable), "\nEquation '{}' should be unsolvable but the outcome of the equation is {:?}", $equation, equation_result);
     };
     ($equation:expr, $($solution:expr),*) => {
         let input = EquationInput::new(String::from($equation), String::from("x"));
+        test_solutions_from_input!(input, $equation, $($solution),*);
+    };
+}
+
+macro_rules! test_solutions_from_input {
+    ($input:expr, $equation:expr, $($solution:expr),*) => {
         let mut expected_solutions = Vec::new();
         $(
             expected_solutions.push($solution as f64);
         )*
-        let actual_solutions = match solve_equation::solve_equation(&input) {
+        let actual_solutions = match solve_input(&$input) {
             Ok(solution) => match solution {
-                Solutions(solutions) => solutions,
+                Solutions { solutions, .. } => solutions.iter().map(Scalar::to_f64).collect::<Vec<f64>>(),
+                ComplexSolutions(solutions) => panic!("\nEquation '{}' has complex solutions {:?} but it should have solutions: {:?}", $equation, solutions, expected_solutions),
                 Unsolvable => panic!("\nEquation '{}' is unsolvable but it should have solutions: {:?}", $equation, expected_solutions),
                 InfiniteSolutions { .. } => panic!("\nEquation '{}' has infinite solutions but it should have finite solutions: {:?}", $equation, expected_solutions),
+                Intervals(intervals) => panic!("\nEquation '{}' has the solution intervals {:?} but it should have solutions: {:?}", $equation, intervals, expected_solutions),
+                ConditionalSolutions { solutions, .. } => panic!("\nEquation '{}' has symbolic solutions {:?} but it should have solutions: {:?}", $equation, solutions, expected_solutions),
+                FilteredSolutions { solutions, .. } => solutions.iter().map(Scalar::to_f64).collect::<Vec<f64>>(),
+                PeriodicSolutions { solutions, .. } => panic!("\nEquation '{}' has periodic solutions {:?} but it should have solutions: {:?}", $equation, solutions, expected_solutions),
+                BoundedSolutions { solutions, .. } => solutions.iter().map(Scalar::to_f64).collect::<Vec<f64>>(),
+                NumericSolutions { solutions, .. } => solutions.iter().map(Scalar::to_f64).collect::<Vec<f64>>(),
             }
-            Err(error) => panic!("\nEquation '{}' should have solutions {:?} but an error occurred: {}", $equation, expected_solutions, error.log_message())
+            Err(error) => panic!("\nEquation '{}' should have solutions {:?} but an error occurred: {}", $equation, expected_solutions, error.log_message($equation))
         };
         assert_eq!(
             expected_solutions.len(),
@@ -60,7 +93,7 @@ macro_rules! test_error {
 
 macro_rules! test_error_from_input {
     ($input:expr, $equation:expr, $error:expr) => {
-        let equation_result = solve_equation::solve_equation(&$input);
+        let equation_result = solve_input(&$input).map_err(|diagnostic| diagnostic.error);
         assert_eq!(
             equation_result,
             Err($error),
@@ -75,7 +108,7 @@ macro_rules! test_error_from_input {
 macro_rules! test_exceptions {
     ($equation:expr) => {
         let input = EquationInput::new(String::from($equation), String::from("x"));
-        let equation_result = solve_equation::solve_equation(&input);
+        let equation_result = solve_input(&input);
         assert_eq!(
             equation_result,
             Ok(InfiniteSolutions { exceptions: Vec::new() }),
@@ -90,21 +123,32 @@ macro_rules! test_exceptions {
         $(
             expected_exceptions.push($exception as f64);
         )*
-        let actual_exceptions = match solve_equation::solve_equation(&input) {
+        let actual_exceptions = match solve_input(&input) {
             Ok(solution) => match solution {
-                Solutions(solutions) => panic!(
+                Solutions { solutions, .. } => panic!(
                     "\nEquation '{}' has solutions {:?} but it should have infinite solutions with exceptions {:?}",
                     $equation, solutions,
                     expected_exceptions
                 ),
+                ComplexSolutions(solutions) => panic!(
+                    "\nEquation '{}' has complex solutions {:?} but it should have infinite solutions with exceptions {:?}",
+                    $equation, solutions,
+                    expected_exceptions
+                ),
                 Unsolvable => panic!("\nEquation '{}' is unsolvable but it should have infinite solutions with exceptions {:?}", $equation, expected_exceptions),
                 InfiniteSolutions { exceptions } => exceptions,
+                Intervals(intervals) => panic!("\nEquation '{}' has the solution intervals {:?} but it should have infinite solutions with exceptions {:?}", $equation, intervals, expected_exceptions),
+                ConditionalSolutions { solutions, .. } => panic!("\nEquation '{}' has symbolic solutions {:?} but it should have infinite solutions with exceptions {:?}", $equation, solutions, expected_exceptions),
+                FilteredSolutions { solutions, .. } => panic!("\nEquation '{}' has the solutions {:?} but it should have infinite solutions with exceptions {:?}", $equation, solutions, expected_exceptions),
+                PeriodicSolutions { solutions, .. } => panic!("\nEquation '{}' has periodic solutions {:?} but it should have infinite solutions with exceptions {:?}", $equation, solutions, expected_exceptions),
+                BoundedSolutions { solutions, .. } => panic!("\nEquation '{}' has the solutions {:?} but it should have infinite solutions with exceptions {:?}", $equation, solutions, expected_exceptions),
+                NumericSolutions { solutions, .. } => panic!("\nEquation '{}' has the numeric solutions {:?} but it should have infinite solutions with exceptions {:?}", $equation, solutions, expected_exceptions),
             }
             Err(error) => panic!(
                 "\nEquation '{}' should have infinite solutions with exception {:?} but an error occurred: {}",
                 $equation,
                 expected_exceptions,
-                error.log_message()
+                error.log_message($equation)
             )
         };
         assert_eq!(
@@ -131,6 +175,54 @@ macro_rules! test_exceptions {
     };
 }
 
+macro_rules! test_output {
+    ($equation:expr, $output:expr) => {
+        test_output!($equation, "x", $output);
+    };
+    ($equation:expr, $variable_name:expr, $output:expr) => {
+        let input = EquationInput::new(String::from($equation), String::from($variable_name));
+        let actual_output = match solve_input(&input) {
+            Ok(result) => result.to_string(),
+            Err(error) => panic!(
+                "\nEquation '{}' should have the solutions {} but an error occurred: {}",
+                $equation,
+                $output,
+                error.log_message($equation)
+            ),
+        };
+        assert_eq!(
+            actual_output, $output,
+            "\nEquation '{}' has the solutions {} but it should have {}",
+            $equation, actual_output, $output
+        );
+    };
+}
+
+macro_rules! test_diagnostic {
+    ($equation:expr, $error:expr, $underline:expr) => {
+        let input = EquationInput::new(String::from($equation), String::from("x"));
+        let diagnostic = match solve_input(&input) {
+            Ok(result) => panic!(
+                "\nEquation '{}' should return error: {:?} but it has the solutions {}",
+                $equation, $error, result
+            ),
+            Err(diagnostic) => diagnostic,
+        };
+        assert_eq!(
+            diagnostic.error, $error,
+            "\nEquation '{}' returned the wrong error",
+            $equation
+        );
+        assert_eq!(
+            diagnostic.span.map(|span| span.underline()),
+            Some(String::from($underline)),
+            "\nEquation '{}' underlines the wrong region:\n{}",
+            $equation,
+            diagnostic.log_message($equation)
+        );
+    };
+}
+
 #[test]
 fn linear_equation() {
     test_solutions!("2x = 6", 3);
@@ -147,6 +239,287 @@ fn quadratic_equation() {
     test_solutions!("1 + 2/x = 4/(x*x)", 1.2360679774997898, -3.23606797749979);
 }
 
+#[test]
+fn cubic_equation() {
+    test_solutions!("x^3 = 8", 2);
+    test_solutions!("x^3 - 6x^2 + 11x - 6 = 0", 3, 2, 1);
+    test_solutions!("(x-1)^3 = 0", 1);
+    test_solutions!("x^3 - 3x + 2 = 0", 1, -2);
+    test_solutions!("x^3 - 5x + 1 = 0", 2.128419, 0.201640, -2.330059);
+    test_solutions!("x = 1/(x*x - 1)", 1.324718);
+}
+
+#[test]
+fn quartic_equation() {
+    test_solutions!("x^4 - 5x^2 + 4 = 0", 2, 1, -1, -2);
+    test_solutions!("(x-1)(x-2)(x-3)(x-4) = 0", 4, 3, 2, 1);
+    test_solutions!("x^4 + x + 1 = 3", 1, -1.3532099641993245);
+    test_solutions!("(x*x + 1)(x - 2)(x + 3) = 0", 2, -3);
+}
+
+#[test]
+fn high_degree_equation() {
+    test_solutions!("x^5 - x = 0", 0, 1, -1);
+    test_solutions!("x^5 + x + 4 = 0", -1.226339);
+    test_solutions!("x^6 - 7x^3 = 8", 2, -1);
+    test_solutions!("(x-1)(x-2)(x-3)(x-4)(x-5) = 0", 5, 4, 3, 2, 1);
+    test_solutions!("1/(x^5 + x + 4) = 1/(x^5 + x + 4) + x", 0);
+    test_output!("(2x - 1)(3x + 2)(x - 5)(x^2 + 1) = 0", "{5, 1/2, -2/3}");
+
+    // Wilkinson's polynomial, whose roots are ill-conditioned but rational
+    let equation = (1..=20)
+        .map(|root| format!("(x - {root})"))
+        .collect::<String>()
+        + " = 0";
+    let mut input = EquationInput::new(equation, String::from("x"));
+    input.settings.max_degree = Some(20);
+    assert_eq!(
+        solve_input(&input).unwrap().to_string(),
+        format!(
+            "{{{}}}",
+            (1..=20)
+                .rev()
+                .map(|root| root.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    );
+}
+
+#[test]
+fn root_isolation() {
+    let tolerance = Tolerance::default();
+    let exact = |value: i32| Scalar::from(value);
+    let mut term = Term::new();
+    // x^5 - 5x^3 + 4x with the roots -2, -1, 0, 1 and 2, squared to check that the repeated
+    // roots are counted once
+    term.addends = HashMap::from([(5, exact(1)), (3, exact(-5)), (1, exact(4))]);
+    let square = {
+        let mut square = term.clone();
+        square.multiply_term(&term);
+        square
+    };
+    for polynomial in [&term, &square] {
+        let sequence = sturm::sequence(polynomial, &tolerance).unwrap();
+        let count = |lower: i32, upper: i32| {
+            sturm::count(
+                &sequence,
+                &BigRational::from_integer(lower.into()),
+                &BigRational::from_integer(upper.into()),
+            )
+        };
+        assert_eq!(count(-3, 3), 5);
+        assert_eq!(count(0, 2), 2);
+        assert_eq!(count(-2, -1), 1);
+        assert_eq!(count(3, 10), 0);
+        let intervals = sturm::isolate(&sequence);
+        assert_eq!(intervals.len(), 5);
+        for (lower, upper) in intervals {
+            assert_eq!(sturm::count(&sequence, &lower, &upper), 1);
+        }
+    }
+
+    test_solutions!(
+        "(x^2 - 2)(x^2 - 2 - 1/1000000000)(x - 3) = 0",
+        3,
+        1.4142135627,
+        std::f64::consts::SQRT_2,
+        -std::f64::consts::SQRT_2,
+        -1.4142135627
+    );
+    test_solutions!(
+        "(x-1)(x-2)(x-3)(x-4)(x-5)(x-6)(x-7)(x-8)(x-9)(x-10) = 0",
+        10,
+        9,
+        8,
+        7,
+        6,
+        5,
+        4,
+        3,
+        2,
+        1
+    );
+    test_solutions!("x^30 - x + 1/10 = 0", 0.996360, 0.1);
+}
+
+#[test]
+fn root_finding_settings() {
+    let mut input = EquationInput::new(String::from("x^3 - 6x^2 + 11x = 6"), String::from("x"));
+    input.settings.root_finding = Numeric;
+    test_solutions_from_input!(input, "x^3 - 6x^2 + 11x = 6", 3, 2, 1);
+
+    let mut input = EquationInput::new(String::from("x^5 + x + 4 = 0"), String::from("x"));
+    input.settings.root_finding = ClosedForm;
+    test_error_from_input!(
+        input,
+        "x^5 + x + 4 = 0",
+        TooHighDegree {
+            degree: 5,
+            max_degree: 4
+        }
+    );
+
+    let mut input = EquationInput::new(String::from("x^3 = 8"), String::from("x"));
+    input.settings = Settings {
+        max_degree: Some(2),
+        ..Settings::default()
+    };
+    test_error_from_input!(
+        input,
+        "x^3 = 8",
+        TooHighDegree {
+            degree: 3,
+            max_degree: 2
+        }
+    );
+}
+
+#[test]
+fn zero_in_unresolved_divisors() {
+    // zero is only excluded if it is a root of a divisor whose other zeroes are unknown
+    for (equation, solutions) in [
+        ("x^2/(x^4 + x) = 0", "{}"),
+        ("x^3/(x^5 + x^2) = 0", "{}"),
+        ("x^2/(x^4 + 1) = 0", "0 (double root)"),
+    ] {
+        let mut input = EquationInput::new(String::from(equation), String::from("x"));
+        input.settings.max_degree = Some(2);
+        let equation_result = solve_input(&input).unwrap();
+        assert_eq!(equation_result.to_string(), solutions, "{equation}");
+    }
+}
+
+#[test]
+fn unresolved_divisors() {
+    let settings = Settings {
+        max_degree: Some(2),
+        ..Settings::default()
+    };
+    for (equation, solutions) in [
+        ("1/(x^3+x+1) = 1/(x^3+x+1) + x", "0"),
+        ("x/(x^3+x+1) = 1/(x^3+x+1)", "1"),
+        ("1/(x^3+x+1) = 1/(x^3+x+1) + x^3 + x + 1", "{}"),
+        ("1/(x^3+x+1) = 1/(x^3+x+1) + x^2 - 1", "{1, -1}"),
+    ] {
+        let mut input = EquationInput::new(String::from(equation), String::from("x"));
+        input.settings = settings.clone();
+        let equation_result = solve_input(&input).unwrap();
+        assert_eq!(equation_result.to_string(), solutions, "{equation}");
+    }
+
+    let mut input = EquationInput::new(String::from("1/(x^3+x+1) > 0"), String::from("x"));
+    input.settings = settings;
+    test_error_from_input!(
+        input,
+        "1/(x^3+x+1) > 0",
+        TooHighDegree {
+            degree: 3,
+            max_degree: 2
+        }
+    );
+}
+
+#[test]
+fn complex_solutions() {
+    let mut input = EquationInput::new(String::from("x^2 - 2x + 5 = 0"), String::from("x"));
+    input.settings.complex_solutions = true;
+    let equation_result = solve_input(&input).unwrap();
+    assert_eq!(
+        equation_result,
+        ComplexSolutions(vec![Complex::new(1.0, 2.0), Complex::new(1.0, -2.0)])
+    );
+    assert_eq!(equation_result.to_string(), "1 ± 2i");
+
+    let mut input = EquationInput::new(String::from("x^4 + 3x^2 = 4"), String::from("x"));
+    input.settings.complex_solutions = true;
+    let equation_result = solve_input(&input).unwrap();
+    assert_eq!(equation_result.to_string(), "{1, -1, ±2i}");
+
+    let mut input = EquationInput::new(String::from("x^2 = 4"), String::from("x"));
+    input.settings.complex_solutions = true;
+    test_solutions_from_input!(input, "x^2 = 4", 2, -2);
+
+    test_solutions!("x^3 + x = 0", 0);
+    test_solutions!("x = 1/(x*x + 1)", 0.682328);
+}
+
+#[test]
+fn multiplicities() {
+    test_output!("(x-1)^2 = 0", "1 (double root)");
+    test_output!("x^3 - 3x + 2 = 0", "{1 (double root), -2}");
+    test_output!("(x-2)^3 = 0", "2 (triple root)");
+    test_output!("x^3*(x-2)^2 = 0", "{0 (triple root), 2 (double root)}");
+    test_output!("x^5*(x + 1) = 0", "{0 (root of multiplicity 5), -1}");
+    test_output!("(x - 3/10)^4 = 0", "3/10 (quadruple root)");
+    test_output!(
+        "(x^2 - 2)^2 = 0",
+        "{√2 (double root), -√2 (double root)} ≈ {1.4142135623730951, -1.4142135623730951}"
+    );
+
+    let mut input = EquationInput::new(String::from("x^2 - 0.2x + 0.01 = 0"), String::from("x"));
+    input.settings.exact_arithmetic = false;
+    assert_eq!(
+        solve_input(&input),
+        Ok(Solutions {
+            solutions: vec![Scalar::Approximate(0.1)],
+            multiplicities: vec![2],
+        })
+    );
+
+    let mut input = EquationInput::new(String::from("(x - 0.1)^3 = 0"), String::from("x"));
+    input.settings.exact_arithmetic = false;
+    match solve_input(&input) {
+        Ok(Solutions {
+            solutions,
+            multiplicities,
+        }) => {
+            assert!(
+                (solutions[0].to_f64() - 0.1).abs() < 1e-6,
+                "{:?}",
+                solutions
+            );
+            assert_eq!(multiplicities, vec![3]);
+        }
+        result => panic!(
+            "\nEquation '(x - 0.1)^3 = 0' should have a triple root but the outcome is {:?}",
+            result
+        ),
+    }
+}
+
+#[test]
+fn rational_expressions() {
+    test_output!("(x^2 - 1)/(x - 1) = 3", "2");
+    test_output!("(x^2 - 1)/(x - 1) = 2", "{}");
+    test_output!("(x^4 - 1)/(x^3 - x^2 + x - 1) = 5", "4");
+    test_output!("(x^2 - 4)/(x - 2) + (x^2 - 9)/(x - 3) = 10", "5/2");
+    test_output!("1/(x - 1) + 2/(x - 1) = 1", "4");
+    test_output!(
+        "1/(x + 1) + 1/(x - 1) = 1",
+        "{1 + √2, 1 - √2} ≈ {2.414213562373095, -0.4142135623730951}"
+    );
+    test_output!("1/(x - 1) + (x + 2) = 5", "2 (double root)");
+    test_output!("x/(x^2 - x) = 2", "3/2");
+    test_output!("1/(1/x + 1) = 2", "-2");
+    test_output!("(x^2 - 1)/(x - 1) > 0", "(-1, 1) ∪ (1, ∞)");
+    test_output!("sqrt(1/(x + 1)) = 2", "-3/4 (removed extraneous -1)");
+    test_exceptions!("(x^2 - 1)/(x - 1) = x + 1", 1);
+    test_error!("log(1/(x + 1)) = 0", TranscendentalEquation);
+
+    let mut input = EquationInput::new(
+        String::from("1/(x - 0.3) + 1/(x - (0.1 + 0.2)) = 1"),
+        String::from("x"),
+    );
+    input.settings.exact_arithmetic = false;
+    test_solutions_from_input!(input, "1/(x - 0.3) + 1/(x - (0.1 + 0.2)) = 1", 2.3);
+
+    let mut input = EquationInput::new(String::from("(x^6 - 1)/(x^3 - 1) = 9"), String::from("x"));
+    input.settings.root_finding = ClosedForm;
+    input.settings.max_degree = Some(3);
+    test_solutions_from_input!(input, "(x^6 - 1)/(x^3 - 1) = 9", 2);
+}
+
 #[test]
 fn factorized_polynomial() {
     test_solutions!("2x*x = 6x", 0, 3);
@@ -161,12 +534,412 @@ fn order_of_operations() {
     test_solutions!("x(1 + 2) = 9", 3);
 }
 
+#[test]
+fn exponentiation() {
+    test_solutions!("x^2 = 4", 2, -2);
+    test_solutions!("2x^2 = 8", 2, -2);
+    test_solutions!("-x^2 = -9", 3, -3);
+    test_solutions!("(x+1)^2 = 0", -1);
+    test_solutions!("x^-2 = 4", 0.5, -0.5);
+    test_solutions!("2^3^2 = x", 512);
+    test_solutions!("x^2/x = 3", 3);
+    test_solutions!("1/(x-1)^2 = 1/(x*x - 2x + 1) + x", 0);
+    test_solutions!("(x+1)^100/(x+1)^99 = 2", 1);
+    test_error!(
+        "(x+1)^100000 = 0",
+        TooHighDegree {
+            degree: 100000,
+            max_degree: 1024
+        }
+    );
+    test_error!("x^(1/100000) = 2", InvalidExponent);
+}
+
+#[test]
+fn exact_arithmetic() {
+    let solve = |equation: &str| {
+        let input = EquationInput::new(String::from(equation), String::from("x"));
+        solve_input(&input).unwrap().to_string()
+    };
+    assert_eq!(solve("3x = 7"), "7/3");
+    assert_eq!(solve("x/3 = 1/3"), "1");
+    assert_eq!(solve("0.1 + 0.2 = x"), "3/10");
+    assert_eq!(solve("4x^2 = 9"), "{3/2, -3/2}");
+    assert_eq!(solve("x^2 - x = 2/x^-1"), "{0, 3}");
+    assert_eq!(solve("x = 2pi"), "6.283185307179586");
+
+    let mut input = EquationInput::new(String::from("3x = 7"), String::from("x"));
+    input.settings.exact_arithmetic = false;
+    let equation_result = solve_input(&input).unwrap();
+    assert_eq!(equation_result.to_string(), "2.3333333333333335");
+}
+
+#[test]
+fn tolerance() {
+    // fractions beyond the range of 64 bit integers are not saturated
+    let fraction = scalar::approximate_fraction(1e20, &Tolerance::default());
+    assert_eq!(fraction, Some(BigRational::from_integer(10.into()).pow(20)));
+    assert_eq!(
+        scalar::approximate_fraction(f64::MAX, &Tolerance::default())
+            .map(|fraction| Scalar::Exact(fraction).to_f64()),
+        Some(f64::MAX)
+    );
+
+    let solve = |equation: &str, tolerance: Tolerance| {
+        let mut input = EquationInput::new(String::from(equation), String::from("x"));
+        input.settings.exact_arithmetic = false;
+        input.settings.tolerance = tolerance;
+        solve_input(&input).unwrap().to_string()
+    };
+    let exact = Tolerance {
+        absolute: 0.0,
+        relative: 0.0,
+    };
+    assert_eq!(
+        solve("(0.1 + 0.2 - 0.3)x^2 + x = 1", Tolerance::default()),
+        "1"
+    );
+    assert_eq!(
+        solve("(0.1 + 0.2 - 0.3)x^2 + x = 1", exact),
+        "{1, -18014398509481984}"
+    );
+    assert_eq!(
+        solve("(x - 0.3)/(x - (0.1 + 0.2)) = 0", Tolerance::default()),
+        "{}"
+    );
+    assert_eq!(solve("(x - 0.3)/(x - (0.1 + 0.2)) = 0", exact), "0.3");
+    assert_eq!(
+        solve("x/(x - 0.3) = x/(x - (0.1 + 0.2))", Tolerance::default()),
+        "R\\{0.30000000000000004}"
+    );
+    assert_eq!(
+        solve("(x - 0.3)/(x - (0.1 + 0.2)) <= 0", Tolerance::default()),
+        "{}"
+    );
+    assert_eq!(solve("(x - 0.3)/(x - (0.1 + 0.2)) <= 0", exact), "{0.3}");
+    assert_eq!(
+        solve("x/(x - 0.3) <= x/(x - (0.1 + 0.2))", Tolerance::default()),
+        "(-∞, 0.3) ∪ (0.3, ∞)"
+    );
+}
+
+#[test]
+fn stable_roots() {
+    let solve = |equation: &str, exact_arithmetic: bool, error_bounds: bool| {
+        let mut input = EquationInput::new(String::from(equation), String::from("x"));
+        input.settings.exact_arithmetic = exact_arithmetic;
+        input.settings.error_bounds = error_bounds;
+        solve_input(&input).unwrap()
+    };
+    let small_root = |result: EquationResult| match result {
+        Solutions { solutions, .. } | BoundedSolutions { solutions, .. } => solutions
+            .iter()
+            .map(Scalar::to_f64)
+            .min_by(|value, other| value.abs().total_cmp(&other.abs()))
+            .unwrap(),
+        result => panic!("\nExpected solutions but the outcome is {:?}", result),
+    };
+    for exact_arithmetic in [true, false] {
+        let root = small_root(solve("x^2 + 100000000x + 1 = 0", exact_arithmetic, false));
+        assert!(((root + 1e-8) / 1e-8).abs() < 1e-14, "{root}");
+        let root = small_root(solve("x^2 - 100000000x + 1 = 0", exact_arithmetic, false));
+        assert!(((root - 1e-8) / 1e-8).abs() < 1e-14, "{root}");
+    }
+    let root = small_root(solve("x^2 + 100000000.5x + 1 = 0", false, false));
+    assert!(((root + 9.99999995e-9) / 1e-8).abs() < 1e-14, "{root}");
+
+    assert_eq!(
+        solve("x^2 + 0.1x - 0.3 = 0", false, true).to_string(),
+        "{0.5 ± 1e-15, -0.6 ± 2e-15}"
+    );
+    assert_eq!(
+        solve("x^2 - 3x + 2.25 = 0", false, true).to_string(),
+        "1.5 ± 2e-7 (double root)"
+    );
+    assert_eq!(solve("x^2 + x = 2", true, true).to_string(), "{1, -2}");
+    assert_eq!(
+        solve("x^4 = 2x", true, true).to_string(),
+        "{0, 1.2599210498948732 ± 3e-15}"
+    );
+}
+
+#[test]
+fn scientific_notation() {
+    test_output!("x = 6.02e23", "602000000000000000000000");
+    test_output!("2x = 1E-9", "1/2000000000");
+    test_output!("x*1.5e2 = 3", "1/50");
+    test_output!("x = 2.5e+3", "2500");
+    test_solutions!("x = 2e", 5.436564);
+    test_solutions!("x = 2e - 1", 4.436564);
+    test_solutions!("x = 3e^2", 22.167168);
+    test_solutions!("x = 1e400/1e399", 10);
+    test_error!("x = 1.2.3", InvalidNumber(String::from("1.2.3")));
+    test_diagnostic!(
+        "x = 1,5.2 + 1",
+        InvalidNumber(String::from("1.5.2")),
+        "    ^~~~~"
+    );
+}
+
+#[test]
+fn unicode_input() {
+    test_output!(
+        "3x² − 2 = 0",
+        "{√6/3, -√6/3} ≈ {0.8164965809277259, -0.8164965809277259}"
+    );
+    test_output!("2 × x = 3 ÷ 4", "3/8");
+    test_output!("2·x⋅3 = 1", "1/6");
+    test_output!("x³ = 8", "2");
+    test_output!("x⁻¹ = 4", "1/4");
+    test_output!("x = ½ + 1⅓", "11/6");
+    test_output!("x = √16", "4");
+    test_output!("x = 2√(3² + 4²)", "10");
+    test_output!("x = ∛27", "3");
+    test_output!("x = √3²", "3");
+    test_output!("x ≠ 2", "(-∞, 2) ∪ (2, ∞)");
+    test_output!("x² ≤ 4", "[-2, 2]");
+    test_error!("x = √-4", InvalidOperation);
+    test_diagnostic!("x = 1 + √ = 2", InvalidOperation, "        ^");
+}
+
+#[test]
+fn locale() {
+    let solve = |equation: &str, locale: Locale| {
+        let mut input = EquationInput::new(String::from(equation), String::from("x"));
+        input.settings.locale = locale;
+        match solve_input(&input) {
+            Ok(result) => result.format(locale),
+            Err(diagnostic) => diagnostic.log_message(equation),
+        }
+    };
+    assert_eq!(solve("1,5x = 3", Locale::European), "2");
+    assert_eq!(solve("x = 1.000,5", Locale::European), "2001/2");
+    assert_eq!(solve("x = 1,000.5", Locale::English), "2001/2");
+    assert_eq!(solve("x = 1,5", Locale::Neutral), "3/2");
+    assert_eq!(
+        solve("x*x = 2,5", Locale::European),
+        "{√10/2; -√10/2} ≈ {1,5811388300841898; -1,5811388300841898}"
+    );
+    assert_eq!(solve("x = 1234567", Locale::English), "1,234,567");
+    assert_eq!(solve("x*x >= 4", Locale::European), "(-∞; -2] ∪ [2; ∞)");
+    assert_eq!(solve("x = 2pi", Locale::European), "6,283185307179586");
+    assert_eq!(
+        solve("2^x = -x^2 - 1", Locale::European),
+        "{} (numeric search in [-100; 100], not exhaustive)"
+    );
+    assert_eq!(
+        solve("x^2 = 12345678", Locale::English),
+        "{3√1,371,742, -3√1,371,742} ≈ {3513.641700572214, -3513.641700572214}"
+    );
+    let mut input = EquationInput::new(String::from("x + y = 3; x - y = 1"), String::from("x, y"));
+    input.settings.locale = Locale::European;
+    assert_eq!(
+        solve_system::solve_system(&input)
+            .unwrap()
+            .format(&input.unknowns(), input.settings.locale),
+        "x = 2; y = 1"
+    );
+    let mut input = EquationInput::new(String::from("x = 1,5"), String::from("x"));
+    input.settings.locale = Locale::English;
+    test_error_from_input!(input, "x = 1,5", InvalidNumber(String::from("1,5")));
+}
+
+#[test]
+fn absolute_values() {
+    test_output!("|2x - 3| = x + 1", "{4, 2/3}");
+    test_output!("abs(x) = 3", "{3, -3}");
+    test_output!("|x - 1| = -2", "{}");
+    test_output!("2|x| - 1 = 5", "{3, -3}");
+    test_output!("||x| - 2| = 1", "{3, 1, -1, -3}");
+    test_output!("|x| = x", "[0, ∞)");
+    test_output!("|x| = |x|", "R");
+    test_output!("|x^2 - 4| = 5", "{3, -3}");
+    test_output!("|x - 1| < 2", "(-1, 3)");
+    test_output!("|x + 2| >= 3", "(-∞, -5] ∪ [1, ∞)");
+    test_output!("|x| ≤ 0", "{0}");
+    test_output!("|1/x| = 2", "{1/2, -1/2}");
+    test_output!("|x - 3| = |x + 1|", "1");
+    test_diagnostic!("|x - 1 = 2", ParenthesisError, "^");
+    test_error!("abs(x = 2)", ParenthesisError);
+    test_error!("|x| = a", SymbolicParameters);
+    test_error!("|x| + a = 2", SymbolicParameters);
+    test_error!("|x - a| = 1", SymbolicParameters);
+}
+
+#[test]
+fn surd_solutions() {
+    let solve = |equation: &str| {
+        let input = EquationInput::new(String::from(equation), String::from("x"));
+        solve_input(&input).unwrap().to_string()
+    };
+    assert_eq!(
+        solve("x^2 = x + 1"),
+        "{(1 + √5)/2, (1 - √5)/2} ≈ {1.618033988749895, -0.6180339887498949}"
+    );
+    assert_eq!(
+        solve("x^2 = 12"),
+        "{2√3, -2√3} ≈ {3.4641016151377544, -3.4641016151377544}"
+    );
+    assert_eq!(
+        solve("x^2 - 4x + 1 = 0"),
+        "{2 + √3, 2 - √3} ≈ {3.732050807568877, 0.2679491924311227}"
+    );
+    assert_eq!(
+        solve("3x^2 = 2"),
+        "{√6/3, -√6/3} ≈ {0.8164965809277259, -0.8164965809277259}"
+    );
+    test_solutions!("x^2 = 3", 1.732051, -1.732051);
+}
+
+#[test]
+fn system_of_equations() {
+    let solve = |equations: &str, unknowns: &str| {
+        let input = EquationInput::new(String::from(equations), String::from(unknowns));
+        solve_system::solve_system(&input)
+            .map(|result| result.format(&input.unknowns(), input.settings.locale))
+            .map_err(|diagnostic| diagnostic.error)
+    };
+    assert_eq!(
+        solve("x + y = 3; x - y = 1", "x, y"),
+        Ok(String::from("x = 2, y = 1"))
+    );
+    assert_eq!(
+        solve(
+            "2a + 3b - c = 5\na - b + 2c = 3\n3a + b + c = 10",
+            "a, b, c"
+        ),
+        Ok(String::from("a = 23/5, b = -2, c = -9/5"))
+    );
+    assert_eq!(
+        solve("x + y = 1; 2x + 2y = 3", "x, y"),
+        Ok(String::from("(x, y) = {}"))
+    );
+    assert_eq!(
+        solve("x + 2y - z = 1; 2x + 4y + z = 5", "x, y, z"),
+        Ok(String::from("x = 2 - 2y, y ∈ R, z = 1"))
+    );
+    assert_eq!(
+        solve("x/2 + y/3 = 1; 3x + 2y = 6", "x, y"),
+        Ok(String::from("x = 2 - 2/3·y, y ∈ R"))
+    );
+    assert_eq!(solve("x*y = 1; x + y = 2", "x, y"), Err(NonlinearSystem));
+    assert_eq!(solve("x/y = 1; x + y = 2", "x, y"), Err(NonlinearSystem));
+    assert_eq!(
+        solve("x^2 + y = 1; x - y = 2", "x, y"),
+        Err(NonlinearSystem)
+    );
+    assert_eq!(
+        solve("1/x + y = 1; x - y = 2", "x, y"),
+        Err(NonlinearSystem)
+    );
+    assert_eq!(
+        solve("x*y*(y - 1/2) + x = 1; y = 1", "x, y"),
+        Err(NonlinearSystem)
+    );
+    assert_eq!(
+        solve("x*y - y*x + x = 1; y = 2", "x, y"),
+        Ok(String::from("x = 1, y = 2"))
+    );
+    assert_eq!(solve("x < 1; x + y = 2", "x, y"), Err(InequalityInSystem));
+    assert_eq!(solve("|x| = 1; x + y = 2", "x, y"), Err(NonlinearSystem));
+}
+
+#[test]
+fn inequalities() {
+    test_output!("2x + 1 > 7", "(3, ∞)");
+    test_output!("x - 4 <= 3x", "[-2, ∞)");
+    test_output!("-3x ≥ 6", "(-∞, -2]");
+    test_output!("x*x < 4", "(-2, 2)");
+    test_output!("x*x >= 4", "(-∞, -2] ∪ [2, ∞)");
+    test_output!("1/(x^2 - 2) < 0", "(-√2, √2)");
+    test_output!("(x - 1)/(x^2 - 3) >= 0", "(-√3, 1] ∪ (√3, ∞)");
+    test_output!("x*x ≤ 0", "{0}");
+    test_output!("x*x + 1 > 0", "R");
+    test_output!("x*x + 1 < 0", "{}");
+    test_output!("x*x < 2", "(-√2, √2)");
+    test_output!("(x+3)(x-1)x > 0", "(-3, 0) ∪ (1, ∞)");
+    test_output!("(x+3)(x-1)x <= 0", "(-∞, -3] ∪ [0, 1]");
+    test_output!("x^3 - x ≥ 0", "[-1, 0] ∪ [1, ∞)");
+}
+
+#[test]
+fn rational_inequalities() {
+    test_output!("1/x > 0", "(0, ∞)");
+    test_output!("1/x < 2", "(-∞, 0) ∪ (1/2, ∞)");
+    test_output!("1/(x-2) >= 0", "(2, ∞)");
+    test_output!("(x+1)/(x-2) <= 0", "[-1, 2)");
+    test_output!("3 < 6/(x+1)", "(-1, 1)");
+    test_output!("x/(x-1) > x/(x-1)", "{}");
+    test_output!("x/(x-1) >= x/(x-1)", "(-∞, 1) ∪ (1, ∞)");
+    test_error!("x < 1 < 2", InvalidSeparatorAmount);
+    test_error!("x <== 1", InvalidSeparator);
+}
+
+#[test]
+fn symbolic_parameters() {
+    test_output!("a*x + b = c", "(c - b)/a for a ≠ 0");
+    test_output!("F = m*a", "a", "F/m for m ≠ 0");
+    test_output!("x + y = 3", "3 - y");
+    test_output!("2x = a", "1/2·a");
+    test_output!("x/a = b", "a·b for a ≠ 0");
+    test_output!("v = s/t", "t", "s/v for v ≠ 0, s ≠ 0");
+    test_output!("a*x + 2x = 4", "4/(a + 2) for a + 2 ≠ 0");
+    test_output!("1/(x - a) = 1", "a + 1");
+    test_output!("x/(a - b) = a - b", "a^2 - 2a·b + b^2 for a - b ≠ 0");
+    test_output!("k*x*x = 0", "0 for k ≠ 0");
+    test_output!("3some_element + 1 = x", "3some_element + 1");
+    test_error!("a*x*x + b*x + c = 0", SymbolicParameters);
+    test_error!("a*x < b", SymbolicParameters);
+    test_error!("x^a = 2", InvalidExponent);
+}
+
+#[test]
+fn error_spans() {
+    test_diagnostic!("3(x+(1+2) = 4", ParenthesisError, " ^");
+    test_diagnostic!("2(x + 1)) = 4", ParenthesisError, "        ^");
+    test_diagnostic!(
+        "1 + 3some$ = x",
+        InvalidElement(String::from("some$")),
+        "     ^~~~~"
+    );
+    test_diagnostic!("3 + x 5 = 2", MissingOperation, "      ^");
+    test_diagnostic!("3 + = x", InvalidSeparator, "    ^");
+    test_diagnostic!("x <= 1 = 2", InvalidSeparatorAmount, "       ^");
+    test_diagnostic!("x ≤ 1 ≤ 2", InvalidSeparatorAmount, "      ^");
+    test_diagnostic!("5 + * x = 8", InvalidOperation, "    ^");
+    test_diagnostic!("x^x = 1", InvalidExponent, "  ^");
+    test_diagnostic!("x = 1/0", DivisionByZero, "      ^");
+    test_diagnostic!("x/(x - x) = 1", DivisionByZero, "  ^~~~~~~");
+    test_diagnostic!("(-1)^0.5 = x", ComplexNumbers, "^~~~~~~~");
+
+    let input = EquationInput::new(String::from("x ≤ 1 ≤ 2"), String::from("x"));
+    let span = solve_input(&input).unwrap_err().span.unwrap();
+    assert_eq!((span.start, span.end), (8, 11));
+    assert_eq!(
+        solve_input(&EquationInput::new(
+            String::from("x = 1/0"),
+            String::from("x")
+        ))
+        .unwrap_err()
+        .log_message("x = 1/0"),
+        "ERROR: Division by zero is undefined\nx = 1/0\n      ^"
+    );
+
+    let input = EquationInput::new(String::from("x + y = 3; x - y = )"), String::from("x, y"));
+    let diagnostic = solve_system::solve_system(&input).unwrap_err();
+    assert_eq!(diagnostic.error, ParenthesisError);
+    assert_eq!(
+        diagnostic.span.map(|span| span.underline()),
+        Some(format!("{}^", " ".repeat(19)))
+    );
+}
+
 #[test]
 fn different_variable_name() {
     let input = EquationInput::new(String::from("2variable + 4 = 8"), String::from("variable"));
     assert_eq!(
-        solve_equation::solve_equation(&input),
-        Ok(Solutions(vec![2.0]))
+        solve_input(&input),
+        Ok(EquationResult::simple_solutions(vec![Scalar::from(2)]))
     );
 }
 
@@ -183,27 +956,373 @@ fn infinite_solutions() {
     test_exceptions!("1/((x-1)(x+1)) = 1/((x-1)(x+1))", 1, -1);
 }
 
+#[test]
+fn divisors_of_unknown_degree() {
+    // zero is excluded by a later divisor which vanishes there, even if an earlier divisor's
+    // zeroes are unknown
+    test_solutions!("x^2/(x^3 + 2)/x = 0");
+    test_solutions!("x^2/(x^3 + 2)/(x + 1) = 0", 0);
+}
+
 #[test]
 fn equation_error() {
     test_error!("", EmptyEquation);
     test_error!("x + 1 = 2", "", EmptyVariableName);
     test_error!("1 + 2 = 3", NoOccurrencesOfVariable);
     test_error!(
-        "1 + 3some_element = x",
-        InvalidElement(String::from("some_element"))
+        "1 + 3some_element$ = x",
+        InvalidElement(String::from("some_element$"))
     );
     test_error!("3 + x 5 = 2", MissingOperation);
     test_error!("3(x+(1+2) = 4", ParenthesisError);
     test_error!("3 + = x", InvalidSeparator);
     test_error!("3 = 1 + 2 = x", InvalidSeparatorAmount);
     test_error!("5 + * x = 8", InvalidOperation);
+    test_error!("x^x = 1", InvalidExponent);
+    test_error!("x^(1/7) = 2", InvalidExponent);
+    test_error!("3 + ^2 = x", InvalidOperation);
     test_error!("x = 1/0", DivisionByZero);
-    test_error!(
-        "2x*x*x + 4 = 0",
-        TooHighDegree {
-            degree: 3,
-            max_degree: 2
+    test_error!("x*x = -1", ComplexNumbers);
+    test_error!("x^4 + 1 = 0", ComplexNumbers);
+}
+
+#[test]
+fn radicals() {
+    test_output!("sqrt(x + 3) = x - 3", "6 (removed extraneous 1)");
+    test_output!("√(x + 3) = x - 3", "6 (removed extraneous 1)");
+    test_solutions!("x^(1/2) = 3", 9);
+    test_solutions!("x^0.5 = 2", 4);
+    test_solutions!("cbrt(x) = -2", -8);
+    test_solutions!("(x - 1)^(2/3) = 4", 9, -7);
+    test_solutions!("sqrt(x) + sqrt(x + 5) = 5", 4);
+    test_solutions!("sqrt(2x + 1) = sqrt(x) + 1", 4, 0);
+    test_solutions!("1/sqrt(x) = 2", 0.25);
+    test_output!("sqrt(x) = -1", "{} (removed extraneous 1)");
+    test_output!("sqrt(x) ≥ 2", "[4, ∞)");
+    test_output!("sqrt(x + 3) < x - 3", "(6, ∞)");
+    test_output!("sqrt(x)^2 = x", "[0, ∞)");
+    test_output!("sqrt(x + 3) > -1", "[-3, ∞)");
+    test_error!("x^(x/2) = 1", InvalidExponent);
+    test_error!("sqrt(x) = a", SymbolicParameters);
+    test_solutions!("sqrt(sqrt(x)) = 2", 16);
+    test_solutions!("(x^(1/2))^(1/2) = 2", 16);
+    test_solutions!("sqrt(1 + sqrt(x)) = 3", 64);
+    test_output!("sqrt(sqrt(x) - 3) = 1", "16");
+    test_error!("sqrt(a)*x = 1", RootOfSymbolicParameter);
+
+    let input = EquationInput::new(
+        String::from("sqrt(x) + y = 2; x + y = 2"),
+        String::from("x, y"),
+    );
+    assert_eq!(
+        solve_system::solve_system(&input).map_err(|diagnostic| diagnostic.error),
+        Err(NonlinearSystem)
+    );
+}
+
+#[test]
+fn exponentials_and_logarithms() {
+    test_output!("2^x = 8", "3");
+    test_output!("3·2^(x + 1) = 48", "3");
+    test_output!("2^-x = 1/8", "3");
+    test_output!("10^(x/2) = 1000", "6");
+    test_solutions!("e^(3x) = 5", 5f64.ln() / 3.0);
+    test_solutions!("exp(x) = 1", 0);
+    test_solutions!("2^(x^2) = 16", 2, -2);
+    test_output!("4^x - 3·2^x + 2 = 0", "{1, 0}");
+    test_solutions!("e^(2x) - 3e^x + 2 = 0", 2f64.ln(), 0);
+    test_solutions!("1/(2^x - 4) = 1", 5f64.log2());
+    test_solutions!("2^x = -1");
+
+    test_output!("log(x) = 2", "100");
+    test_solutions!("ln(x) = 1", std::f64::consts::E);
+    test_output!("log_2(x + 1) = 3", "7");
+    test_output!("log(x) + log(x - 3) = 1", "5 (removed extraneous -2)");
+    test_output!("log(x^2) = 2log(x)", "(0, ∞)");
+
+    test_output!("2^x > 8", "(3, ∞)");
+    test_output!("ln(x) < 0", "(0, 1)");
+
+    test_error!("x + 2^x > 3", TranscendentalEquation);
+    test_error!("2^x + 3^x < 5", TranscendentalEquation);
+    test_output!("log(x)^2 - log(x) = 0", "{10, 1}");
+    test_error!("log(x)·log(x + 1) ≥ 1", TranscendentalEquation);
+    test_error!("(-2)^x = 4", ComplexNumbers);
+    test_error!("log(-1) = x", ComplexNumbers);
+    test_error!("log(0) = x", LogarithmOfZero);
+    test_error!("ln(1 - 1) + x = 1", LogarithmOfZero);
+    test_error!("log(a) = x", SymbolicParameters);
+    test_diagnostic!("x^x = 1", InvalidExponent, "  ^");
+    test_diagnostic!(
+        "log_1(x) = 2",
+        InvalidElement(String::from("log_1")),
+        "^~~~~"
+    );
+}
+
+#[test]
+fn trigonometric_functions() {
+    test_output!("sin(x) = 1/2", "π/6 + 2kπ, 5π/6 + 2kπ");
+    test_output!("tan(x) = 1", "π/4 + kπ");
+    test_output!("2cos(x)^2 - cos(x) - 1 = 0", "2kπ, 2π/3 + 2kπ, 4π/3 + 2kπ");
+    test_output!("sin(x + pi/3) = 0", "2π/3 + 2kπ, 5π/3 + 2kπ");
+    test_output!("sin(2x) = 0", "kπ, π/2 + kπ");
+    test_output!("cos(x) = 2", "{}");
+    test_output!("asin(x) = pi/6", "1/2");
+    test_output!("cos(pi/3)·x = 1", "2");
+    test_output!("arctan(x) >= 0", "[0, ∞)");
+    test_solutions!("acos(x) = 3", 3f64.cos());
+    test_solutions!("asin(x) = 2");
+
+    let solve = |equation: &str| {
+        let mut input = EquationInput::new(String::from(equation), String::from("x"));
+        input.settings.angle_unit = AngleUnit::Degrees;
+        solve_input(&input).map(|result| result.to_string())
+    };
+    assert_eq!(solve("sin(x) = 1/2").unwrap(), "30 + 360k, 150 + 360k");
+    assert_eq!(solve("tan(x) = -1").unwrap(), "135 + 180k");
+    assert_eq!(solve("x = sin(30)").unwrap(), "1/2");
+    assert_eq!(solve("asin(x) = 30").unwrap(), "1/2");
+
+    let input = EquationInput::new(String::from("cos(x) = 0"), String::from("x"));
+    let result = solve_input(&input).unwrap();
+    let members = result
+        .list_between(0.0, 2.0 * std::f64::consts::PI)
+        .unwrap();
+    assert_eq!(members.len(), 2);
+    assert!((members[1].to_f64() - 1.5 * std::f64::consts::PI).abs() < TOLERANCE);
+
+    // large values of the tangent are only reached close to its poles
+    for (equation, value) in [
+        ("tan(x) = 10000", 1e4_f64),
+        ("tan(x) = 100000000", 1e8),
+        ("tan(x) = 1e20", 1e20),
+    ] {
+        let input = EquationInput::new(String::from(equation), String::from("x"));
+        let solutions = match solve_input(&input) {
+            Ok(PeriodicSolutions { solutions, .. }) => solutions,
+            result => panic!("\nEquation '{equation}' should have periodic solutions: {result:?}"),
+        };
+        assert_eq!(solutions.len(), 1, "{equation}");
+        let turns = (solutions[0].to_f64() - value.atan()) / std::f64::consts::PI;
+        assert!((turns - turns.round()).abs() < 1e-12, "{equation}");
+    }
+    test_output!("1e20·sin(x) = 5e19", "π/6 + 2kπ, 5π/6 + 2kπ");
+
+    test_error!("sin(x) > 0", PeriodicInequality);
+    test_error!("sin(x) < cos(x)", PeriodicInequality);
+    test_error!("tan(pi/2) = x", DivisionByZero);
+}
+
+#[test]
+fn numeric_solutions() {
+    test_output!(
+        "x + 2^x = 3",
+        "1 (numeric search in [-100, 100], not exhaustive)"
+    );
+    test_solutions!("e^x = 3x", 1.5121345516578424, 0.6190612867359452);
+    test_solutions!("1/(x - 1) + 2^x = 0", 0, -1);
+    test_solutions!("2^x + 3^x = 5", 1);
+    test_solutions!("(x - 1)^2·e^x = 0", 1);
+    test_solutions!("sqrt(x) = 2^(-x)", 0.5);
+    test_output!(
+        "2^x = -x^2 - 1",
+        "{} (numeric search in [-100, 100], not exhaustive)"
+    );
+
+    // an exact zero of the function is reported with the precision instead of the bracket width
+    let input = EquationInput::new(String::from("x = cos(x)"), String::from("x"));
+    match solve_input(&input) {
+        Ok(NumericSolutions {
+            solutions,
+            tolerances,
+            ..
+        }) => {
+            assert_eq!(solutions.len(), 1);
+            assert!((solutions[0].to_f64() - 0.7390851332151607).abs() < 1e-12);
+            assert!(tolerances[0] <= 1e-12, "{tolerances:?}");
         }
+        result => panic!("'x = cos(x)' should have a numeric solution: {result:?}"),
+    }
+
+    test_output!("sin(x)^2 + cos(x)^2 = 1", "R");
+    test_output!("cos(x)^2 - 1 + sin(x)^2 = 0", "R");
+
+    let mut input = EquationInput::new(
+        String::from("sin(x)^2 + cos(x)^2 + ln(x) - ln(x) = 1"),
+        String::from("x"),
     );
-    test_error!("x*x = -1", ComplexNumbers);
+    input.settings.solution_range = Some((-5.0, 5.0));
+    let intervals = match solve_input(&input) {
+        Ok(Intervals(intervals)) => intervals,
+        result => panic!("the solutions should be an interval: {result:?}"),
+    };
+    assert_eq!(intervals.len(), 1);
+    match (&intervals[0].lower, &intervals[0].upper) {
+        (Closed(lower), Closed(upper)) => {
+            assert!(lower.to_f64() > 0.0 && lower.to_f64() < 1e-3);
+            assert_eq!(upper.to_f64(), 5.0);
+        }
+        bounds => panic!("the solutions should be a closed interval: {bounds:?}"),
+    }
+
+    let mut input = EquationInput::new(String::from("tan(x) = x"), String::from("x"));
+    input.settings.solution_range = Some((-5.0, 5.0));
+    test_solutions_from_input!(
+        input,
+        "tan(x) = x",
+        4.493409457909064,
+        0,
+        -4.493409457909064
+    );
+
+    for equation in [
+        "e^x = 3x",
+        "sin(x) = cos(x)",
+        "x·sin(x) = 1",
+        "sin(x^2) = 0",
+    ] {
+        let mut input = EquationInput::new(String::from(equation), String::from("x"));
+        input.settings.root_finding = ClosedForm;
+        test_error_from_input!(input, equation, TranscendentalEquation);
+    }
+}
+
+#[test]
+fn back_substitution() {
+    let input = EquationInput::new(String::from("1/(x + 1) + 1/(x - 1) = 1"), String::from("x"));
+    let (_, verification) = solve_equation::solve_equation(&input).unwrap();
+    assert_eq!(verification.solutions.len(), 2);
+    assert!(verification
+        .residuals
+        .iter()
+        .all(|residual| *residual < 1e-12));
+    assert!(verification.rejected.is_empty());
+
+    let verify = |equation: &str, candidates: Vec<i32>| {
+        let input = EquationInput::new(String::from(equation), String::from("x"));
+        let original_equation = OriginalEquation::parse(&input.token_stream().unwrap()).unwrap();
+        let multiplicities = vec![1; candidates.len()];
+        let candidates = Solutions {
+            solutions: candidates.into_iter().map(Scalar::from).collect(),
+            multiplicities,
+        };
+        verification::verify(candidates, &original_equation, &input.settings)
+    };
+
+    let (result, verification) = verify("x/(x - 1) = 2", vec![1, 2]);
+    assert_eq!(result.to_string(), "2");
+    assert_eq!(verification.reasons, vec![Rejection::DivisionByZero]);
+    assert_eq!(
+        verification.format_rejections("x", Locale::Neutral),
+        vec!["removed x = 1: division by zero in the original equation"]
+    );
+    assert_eq!(
+        verification.format_residuals("x", Locale::Neutral).unwrap(),
+        "|lhs - rhs| = 0 at x = 2"
+    );
+
+    let (result, verification) = verify("x^2 = 2/x^-1", vec![0, 2, 3]);
+    assert_eq!(result.to_string(), "{0, 2}");
+    assert_eq!(verification.reasons, vec![Rejection::Residual(3.0)]);
+
+    let (result, verification) = verify("sqrt(x) + log(x + 5) = 1", vec![-4, 1]);
+    assert_eq!(result, Unsolvable);
+    assert_eq!(
+        verification.format_rejections("x", Locale::Neutral),
+        vec![
+            "removed x = -4: the original equation is undefined there",
+            "removed x = 1: the residual 8e-1 in the original equation is too large"
+        ]
+    );
+
+    let (result, verification) = verify("||x| - 2| = x", vec![-3, 1]);
+    assert_eq!(result.to_string(), "1");
+    assert_eq!(verification.reasons, vec![Rejection::Residual(4.0)]);
+
+    // the residual is compared with the slope times the precision of the root, so a well
+    // conditioned root has to be accurate while a root close to a pole can leave a large residual
+    let input = EquationInput::new(String::from("x^2 = 2"), String::from("x"));
+    let original_equation = OriginalEquation::parse(&input.token_stream().unwrap()).unwrap();
+    let candidates = Solutions {
+        solutions: vec![Scalar::Approximate(2f64.sqrt() + 1e-9)],
+        multiplicities: vec![1],
+    };
+    let (result, _) = verification::verify(candidates, &original_equation, &input.settings);
+    assert_eq!(result, Unsolvable);
+
+    let input = EquationInput::new(String::from("tan(x)^2 = 1e16"), String::from("x"));
+    let (result, verification) = solve_equation::solve_equation(&input).unwrap();
+    assert!(matches!(result, PeriodicSolutions { solutions, .. } if solutions.len() == 2));
+    assert!(verification.rejected.is_empty());
+
+    // the expanded polynomial of (x - 1)^60 has coefficients around 1e17, whose rounding errors
+    // swamp its value near the root, while the original sides are evaluated as written
+    let input = EquationInput::new(String::from("(x - 1)^60 = 0"), String::from("x"));
+    let (_, verification) = solve_equation::solve_equation(&input).unwrap();
+    assert_eq!(
+        verification.format_residuals("x", Locale::Neutral).unwrap(),
+        "|lhs - rhs| = 0 at x = 1"
+    );
+    let tokens = input.token_stream().unwrap();
+    let original_equation = OriginalEquation::parse(&tokens).unwrap();
+    let (difference, _) = original_equation
+        .difference(1.5, &input.settings)
+        .unwrap()
+        .unwrap();
+    assert_eq!(difference, 0.5f64.powi(60));
+    let (term, denominator) =
+        Equation::expression(tokens[..tokens.len() - 2].to_vec(), &input.settings).unwrap();
+    assert!((term.evaluate(1.5) / denominator.evaluate(1.5) - difference).abs() > 1.0);
+
+    let input = EquationInput::new(String::from("x = 1e400/1e399"), String::from("x"));
+    let (result, verification) = solve_equation::solve_equation(&input).unwrap();
+    assert_eq!(result.to_string(), "10");
+    assert_eq!(verification.unverified, vec![Scalar::from(10)]);
+    assert_eq!(
+        verification
+            .format_unverified("x", Locale::Neutral)
+            .unwrap(),
+        "not verified at x = 10: the original equation could not be evaluated there"
+    );
+
+    let input = EquationInput::new(String::from("a·x = 1"), String::from("x"));
+    let (_, verification) = solve_equation::solve_equation(&input).unwrap();
+    assert!(verification.solutions.is_empty());
+}
+
+#[test]
+fn original_sides_agree_with_terms() {
+    // the evaluator of the original sides reads the tokens with the precedence the terms of the
+    // equation are built with
+    let settings = Settings::default();
+    for expression in [
+        "2/x^-1",
+        "-x^2 + 3x",
+        "2^3^2·x",
+        "4/2x",
+        "x^-2·4/2x",
+        "2^-2x",
+        "(x + 1)(x - 2)/(x - 3)",
+        "3x(x + 1)^2 - x/4/2",
+        "-(x - 1)^3/x^2 + 1/(x + 1)^-2",
+        "x²·½ - 6.02e2x",
+    ] {
+        let input = EquationInput::new(format!("{expression} = 0"), String::from("x"));
+        let tokens = input.token_stream().unwrap();
+        let original_equation = OriginalEquation::parse(&tokens).unwrap();
+        let (term, denominator) =
+            Equation::expression(tokens[..tokens.len() - 2].to_vec(), &settings).unwrap();
+        for value in [-2.5, 0.5, 1.5, 4.0] {
+            let (difference, _) = original_equation
+                .difference(value, &settings)
+                .unwrap()
+                .unwrap();
+            let expected = term.evaluate(value) / denominator.evaluate(value);
+            assert!(
+                (difference - expected).abs() <= 1e-12 * expected.abs().max(1.0),
+                "{expression} at {value}: {difference} != {expected}"
+            );
+        }
+    }
 }
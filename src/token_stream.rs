@@ -1,19 +1,28 @@
+use std::collections::HashMap;
 use std::f64::consts::E;
 use std::f64::consts::PI;
 
 use crate::equation_element::{
-    AdditiveOperationType::*,
     EquationElement::{self, *},
-    MultiplicativeOperationType::*,
-    OperationType::*,
+    Function::{self, *},
+    OperationType::Exponentiation,
     SymbolType::*,
+    Token,
     ValueType::*,
 };
-use crate::equation_error::EquationError::{self, *};
+use crate::equation_error::{
+    Diagnostic,
+    EquationError::{self, *},
+};
+use crate::expression::Expression;
+use crate::lexer::{expand_roots, is_function, Lexeme, LexemeKind, Lexer};
+use crate::scalar::Scalar;
+use crate::settings::Settings;
 
 pub struct EquationInput {
     equation: String,
     pub variable_name: String,
+    pub settings: Settings,
 }
 
 impl EquationInput {
@@ -21,88 +30,213 @@ impl EquationInput {
         Self {
             equation,
             variable_name,
+            settings: Settings::default(),
         }
     }
 
-    pub fn token_stream(&self) -> Result<Vec<EquationElement>, EquationError> {
+    pub fn token_stream(&self) -> Result<Vec<Token>, Diagnostic> {
         if self.equation.is_empty() {
-            return Err(EmptyEquation);
+            return Err(EmptyEquation.into());
         }
         if self.variable_name.is_empty() {
-            return Err(EmptyVariableName);
+            return Err(EmptyVariableName.into());
         }
 
-        let mut elements = Vec::new();
-        let mut current_value = String::new();
-        let mut number = true;
-        for element in self.equation.chars() {
-            let element = match element {
-                ' ' => {
-                    add_value_to_elements(&mut elements, &mut current_value, &self.variable_name)?;
-                    number = true;
-                    continue;
+        self.tokenize(&self.equation, &self.variable_name, &HashMap::new(), true)
+    }
+
+    pub fn equation(&self) -> &str {
+        &self.equation
+    }
+
+    // the byte and the character offset of an equation of the system in the whole input
+    pub fn offset_of(&self, equation: &str) -> (usize, usize) {
+        let bytes = (equation.as_ptr() as usize).saturating_sub(self.equation.as_ptr() as usize);
+        let characters = self
+            .equation
+            .get(..bytes)
+            .unwrap_or_default()
+            .chars()
+            .count();
+        (bytes, characters)
+    }
+
+    pub fn is_system(&self) -> bool {
+        self.equations().len() > 1 || self.unknowns().len() > 1
+    }
+
+    pub fn equations(&self) -> Vec<&str> {
+        self.equation
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|equation| !equation.is_empty())
+            .collect()
+    }
+
+    pub fn unknowns(&self) -> Vec<String> {
+        self.variable_name
+            .split(',')
+            .map(|unknown| unknown.trim().to_string())
+            .collect()
+    }
+
+    // the unknowns of a system other than the variable are replaced by the substituted numbers and
+    // other identifiers are not treated as symbolic parameters
+    pub fn system_token_stream(
+        &self,
+        equation: &str,
+        variable_name: &str,
+        substitutions: &HashMap<String, Scalar>,
+    ) -> Result<Vec<Token>, Diagnostic> {
+        self.tokenize(equation, variable_name, substitutions, false)
+    }
+
+    fn tokenize(
+        &self,
+        equation: &str,
+        variable_name: &str,
+        substitutions: &HashMap<String, Scalar>,
+        parameters: bool,
+    ) -> Result<Vec<Token>, Diagnostic> {
+        let lexemes = Lexer::new(equation, self.settings.locale)
+            .collect::<Result<Vec<Lexeme>, Diagnostic>>()?;
+        let mut lexemes = expand_roots(lexemes)?.into_iter().peekable();
+        let mut tokens: Vec<Token> = Vec::new();
+        // whether each open parenthesis belongs to an absolute value written as abs(...)
+        let mut parentheses = Vec::new();
+        let mut open_bars = 0;
+        while let Some(Lexeme { kind, mut span }) = lexemes.next() {
+            let element = match kind {
+                LexemeKind::Number(number) => {
+                    match Scalar::parse(&number, self.settings.exact_arithmetic) {
+                        Some(value) => Value(Number(value)),
+                        None => return Err(InvalidNumber(number).at(span)),
+                    }
                 }
-                '+' => Operation(AdditiveOperation(Addition)),
-                '-' => Operation(AdditiveOperation(Subtraction)),
-                '*' => Operation(MultiplicativeOperation(Multiplication)),
-                '/' => Operation(MultiplicativeOperation(Division)),
-                '=' => Separator,
-                '(' => OpeningParenthesis,
-                ')' => ClosingParenthesis,
-                '.' | ',' => {
-                    current_value.push('.');
-                    continue;
+                LexemeKind::Word(word)
+                    if word == "abs"
+                        && matches!(
+                            lexemes.peek(),
+                            Some(Lexeme {
+                                kind: LexemeKind::Element(OpeningParenthesis),
+                                ..
+                            })
+                        ) =>
+                {
+                    span = span.join(&lexemes.next().unwrap().span);
+                    parentheses.push(true);
+                    OpeningAbsoluteValue
                 }
-                'π' => Value(Symbol(Constant(PI))),
-                _ => {
-                    if current_value.is_empty() && !element.is_digit(10) {
-                        number = false;
+                LexemeKind::Word(word)
+                    if is_function(&word)
+                        && matches!(
+                            lexemes.peek(),
+                            Some(Lexeme {
+                                kind: LexemeKind::Element(OpeningParenthesis),
+                                ..
+                            })
+                        ) =>
+                {
+                    // exp(...) is written as e^(...)
+                    if word == "exp" {
+                        tokens.push(Token {
+                            element: Value(Symbol(Constant(Scalar::Approximate(E)))),
+                            span,
+                        });
+                        Operation(Exponentiation)
+                    } else {
+                        let function = function(&word, self.settings.exact_arithmetic)
+                            .ok_or(InvalidElement(word).at(span))?;
+                        span = span.join(&lexemes.next().unwrap().span);
+                        parentheses.push(false);
+                        OpeningFunction(function)
                     }
-                    if number && !current_value.is_empty() && !element.is_digit(10) {
-                        add_value_to_elements(
-                            &mut elements,
-                            &mut current_value,
-                            &self.variable_name,
-                        )?;
-                        number = false;
+                }
+                LexemeKind::Word(word) => {
+                    resolve_word(&word, variable_name, substitutions, parameters)
+                        .map_err(|error| error.at(span))?
+                }
+                LexemeKind::Element(OpeningParenthesis) => {
+                    parentheses.push(false);
+                    OpeningParenthesis
+                }
+                LexemeKind::Element(ClosingParenthesis) => match parentheses.pop() {
+                    Some(true) => ClosingAbsoluteValue,
+                    _ => ClosingParenthesis,
+                },
+                LexemeKind::Element(element) => element,
+                // a bar closes an absolute value if one is open and a value precedes it
+                LexemeKind::Bar => {
+                    let closing = open_bars > 0
+                        && matches!(
+                            tokens.last(),
+                            Some(Token {
+                                element: Value(_) | ClosingParenthesis | ClosingAbsoluteValue,
+                                ..
+                            })
+                        );
+                    match closing {
+                        true => {
+                            open_bars -= 1;
+                            ClosingAbsoluteValue
+                        }
+                        false => {
+                            open_bars += 1;
+                            OpeningAbsoluteValue
+                        }
                     }
-                    current_value.push(element);
-                    continue;
                 }
+                LexemeKind::Root(_) => unreachable!(),
             };
-            add_value_to_elements(&mut elements, &mut current_value, &self.variable_name)?;
-            number = true;
-            elements.push(element);
+            tokens.push(Token { element, span });
         }
-        add_value_to_elements(&mut elements, &mut current_value, &self.variable_name)?;
-        Ok(elements)
+        Ok(tokens)
     }
 }
 
-fn add_value_to_elements(
-    elements: &mut Vec<EquationElement>,
-    value: &mut String,
+fn resolve_word(
+    word: &str,
     variable_name: &str,
-) -> Result<(), EquationError> {
-    if value.is_empty() {
-        return Ok(());
-    }
-    if &**value == "i" {
-        return Err(ComplexNumbers);
+    substitutions: &HashMap<String, Scalar>,
+    parameters: bool,
+) -> Result<EquationElement, EquationError> {
+    Ok(Value(match word {
+        "i" => return Err(ComplexNumbers),
+        _ if word == variable_name => Symbol(Variable),
+        _ if substitutions.contains_key(word) => Symbol(Constant(substitutions[word].clone())),
+        "pi" => Symbol(Constant(Scalar::Approximate(PI))),
+        "e" => Symbol(Constant(Scalar::Approximate(E))),
+        _ if parameters && is_identifier(word) => {
+            Symbol(Constant(Scalar::from(Expression::parameter(word))))
+        }
+        _ => return Err(InvalidElement(word.to_owned())),
+    }))
+}
+
+// log is the common and ln the natural logarithm, log_b has a positive base other than one
+fn function(word: &str, exact_arithmetic: bool) -> Option<Function> {
+    match word {
+        "sin" => Some(Sine),
+        "cos" => Some(Cosine),
+        "tan" => Some(Tangent),
+        "asin" | "arcsin" => Some(Arcsine),
+        "acos" | "arccos" => Some(Arccosine),
+        "atan" | "arctan" => Some(Arctangent),
+        "log" => Some(Logarithm(Scalar::from(10))),
+        "ln" => Some(Logarithm(Scalar::Approximate(E))),
+        _ => word
+            .strip_prefix("log_")
+            .and_then(|base| match base {
+                "e" => Some(Scalar::Approximate(E)),
+                _ => Scalar::parse(base, exact_arithmetic),
+            })
+            .filter(|base| base.to_f64() > 0.0 && base.to_f64() != 1.0)
+            .map(Logarithm),
     }
+}
 
-    let element = Value(match &**value {
-        _ if &**value == variable_name => Symbol(Variable),
-        "pi" => Symbol(Constant(PI)),
-        "e" => Symbol(Constant(E)),
-        _ => match value.parse::<f64>() {
-            Ok(value) => Number(value),
-            Err(_) => {
-                return Err(InvalidElement(value.to_owned()));
-            }
-        },
-    });
-    elements.push(element);
-    *value = String::new();
-    Ok(())
+fn is_identifier(value: &str) -> bool {
+    let mut characters = value.chars();
+    matches!(characters.next(), Some(first) if first.is_alphabetic() || first == '_')
+        && characters.all(|character| character.is_alphanumeric() || character == '_')
 }
@@ -0,0 +1,719 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive};
+use std::collections::HashMap;
+
+use crate::equation_element::{
+    Function::{self, Arccosine, Arcsine, Arctangent, Cosine, Sine, Tangent},
+    RelationType::{self, *},
+};
+use crate::equation_error::EquationError::{self, *};
+use crate::equation_result::EquationResult::{self, *};
+use crate::exceptions_in_domain::ExceptionsInDomain::*;
+use crate::expression::Expression;
+use crate::inequality;
+use crate::interval;
+use crate::scalar::{approximate_fraction, Scalar};
+use crate::settings::{AngleUnit, Settings, Tolerance};
+use crate::term::Term;
+use crate::trigonometry;
+
+use Transcendental::*;
+
+// the largest denominator of exact logarithms which are looked for
+const MAX_DENOMINATOR: i32 = 12;
+// exact logarithms are looked for with exponents up to this bound
+const MAX_EXACT_EXPONENT: i32 = 64;
+
+#[derive(Clone, Debug)]
+pub enum Transcendental {
+    // the positive constant base raised to an exponent which depends on the variable
+    Exponential {
+        base: Scalar,
+        exponent: Term,
+    },
+    // the logarithm of an argument which depends on the variable to a positive constant base
+    Logarithm {
+        base: Scalar,
+        argument: Term,
+    },
+    // a trigonometric function or its inverse of an argument which depends on the variable
+    Trigonometric {
+        function: Function,
+        argument: Term,
+        unit: AngleUnit,
+    },
+}
+
+impl Transcendental {
+    // the symbolic parameter standing for the function, which cannot clash with an identifier
+    fn name(&self, position: usize) -> String {
+        match self {
+            Exponential { .. } => format!("exp({position})"),
+            Logarithm { .. } => format!("log({position})"),
+            Trigonometric { .. } => format!("trig({position})"),
+        }
+    }
+
+    fn is_periodic(&self) -> bool {
+        matches!(
+            self,
+            Trigonometric {
+                function: Sine | Cosine | Tangent,
+                ..
+            }
+        )
+    }
+
+    fn is_same(&self, other: &Self) -> bool {
+        let same_term = |term: &Term, other: &Term| {
+            term.addends
+                .keys()
+                .chain(other.addends.keys())
+                .all(|power| term.coefficient(*power) == other.coefficient(*power))
+        };
+        match (self, other) {
+            (
+                Exponential { base, exponent },
+                Exponential {
+                    base: other_base,
+                    exponent: other_exponent,
+                },
+            ) => base == other_base && same_term(exponent, other_exponent),
+            (
+                Logarithm { base, argument },
+                Logarithm {
+                    base: other_base,
+                    argument: other_argument,
+                },
+            ) => base == other_base && same_term(argument, other_argument),
+            (
+                Trigonometric {
+                    function, argument, ..
+                },
+                Trigonometric {
+                    function: other_function,
+                    argument: other_argument,
+                    ..
+                },
+            ) => function == other_function && same_term(argument, other_argument),
+            _ => false,
+        }
+    }
+
+    fn parameter(&self, position: usize) -> Term {
+        let mut term = Term::new_multiplier();
+        term.multiply_constant(&Scalar::from(Expression::parameter(&self.name(position))));
+        term
+    }
+
+    fn evaluate(&self, value: f64) -> Option<f64> {
+        match self {
+            Exponential { base, exponent } => Some(base.to_f64().powf(exponent.evaluate(value))),
+            Logarithm { base, argument } => {
+                let argument = argument.evaluate(value);
+                (argument > 0.0).then(|| argument.ln() / base.to_f64().ln())
+            }
+            Trigonometric {
+                function,
+                argument,
+                unit,
+            } => trigonometry::evaluate(function, argument.evaluate(value), *unit),
+        }
+    }
+}
+
+// the parameter of an equal function which was replaced before or of the new one
+fn register(transcendental: Transcendental, transcendentals: &mut Vec<Transcendental>) -> Term {
+    match transcendentals
+        .iter()
+        .position(|other| other.is_same(&transcendental))
+    {
+        Some(position) => transcendentals[position].parameter(position),
+        None => {
+            transcendentals.push(transcendental);
+            transcendentals[transcendentals.len() - 1].parameter(transcendentals.len() - 1)
+        }
+    }
+}
+
+// Replaces the power with a variable exponent by a new symbolic parameter
+pub fn exponential(
+    base: &Scalar,
+    exponent: &Term,
+    transcendentals: &mut Vec<Transcendental>,
+    settings: &Settings,
+) -> Result<Term, EquationError> {
+    if base.is_symbolic()
+        || exponent.is_symbolic()
+        || exponent.lowest_exponent(&settings.tolerance) < 0
+    {
+        return Err(InvalidExponent);
+    }
+    if base.is_negative() {
+        return Err(ComplexNumbers);
+    }
+    if base.is_zero() {
+        return Err(InvalidExponent);
+    }
+    if base.to_f64() == 1.0 {
+        return Ok(Term::new_multiplier());
+    }
+
+    let mut power = register(
+        Exponential {
+            base: base.clone(),
+            exponent: exponent.clone(),
+        },
+        transcendentals,
+    );
+    power.extend_exceptions(exponent, &settings.tolerance);
+    Ok(power)
+}
+
+pub fn apply(
+    function: &Function,
+    argument: &Term,
+    transcendentals: &mut Vec<Transcendental>,
+    settings: &Settings,
+) -> Result<Term, EquationError> {
+    match function {
+        Function::Logarithm(base) => logarithm(base, argument, transcendentals, settings),
+        _ => trigonometric(function, argument, transcendentals, settings),
+    }
+}
+
+// Replaces the logarithm by a new symbolic parameter and restricts the domain to where its
+// argument is positive
+fn logarithm(
+    base: &Scalar,
+    argument: &Term,
+    transcendentals: &mut Vec<Transcendental>,
+    settings: &Settings,
+) -> Result<Term, EquationError> {
+    check_argument(argument, transcendentals)?;
+    if let Some(constant) = argument.constant() {
+        let mut value = Term::new_multiplier();
+        value.multiply_constant(&logarithm_of(&constant, base).ok_or(
+            match constant.is_zero() {
+                true => LogarithmOfZero,
+                false => ComplexNumbers,
+            },
+        )?);
+        value.extend_exceptions(argument, &settings.tolerance);
+        return Ok(value);
+    }
+
+    let mut value = register(
+        Logarithm {
+            base: base.clone(),
+            argument: argument.clone(),
+        },
+        transcendentals,
+    );
+    value.extend_exceptions(argument, &settings.tolerance);
+    restrict_domain(&mut value, argument, GreaterThan, settings)?;
+    Ok(value)
+}
+
+// Replaces the trigonometric function by a new symbolic parameter. The inverse sine and cosine
+// restrict the domain to where the argument is between -1 and 1
+fn trigonometric(
+    function: &Function,
+    argument: &Term,
+    transcendentals: &mut Vec<Transcendental>,
+    settings: &Settings,
+) -> Result<Term, EquationError> {
+    check_argument(argument, transcendentals)?;
+    if let Some(constant) = argument.constant() {
+        let mut value = Term::new_multiplier();
+        value.multiply_constant(&trigonometry::value_of(
+            function,
+            &constant,
+            settings.angle_unit,
+            &settings.tolerance,
+        )?);
+        value.extend_exceptions(argument, &settings.tolerance);
+        return Ok(value);
+    }
+
+    let mut value = register(
+        Trigonometric {
+            function: function.clone(),
+            argument: argument.clone(),
+            unit: settings.angle_unit,
+        },
+        transcendentals,
+    );
+    value.extend_exceptions(argument, &settings.tolerance);
+    if let Arcsine | Arccosine = function {
+        let mut bound = argument.clone();
+        bound.multiply_term(argument);
+        bound.multiply_constant(&Scalar::from(-1));
+        bound.add_term(&Term::new_multiplier());
+        restrict_domain(&mut value, &bound, GreaterThanOrEqual, settings)?;
+    }
+    Ok(value)
+}
+
+// arguments with symbolic parameters cannot be handled and neither can nested functions
+fn check_argument(
+    argument: &Term,
+    transcendentals: &[Transcendental],
+) -> Result<(), EquationError> {
+    if !argument.is_symbolic() {
+        return Ok(());
+    }
+    let nested = argument.addends.values().any(|coefficient| {
+        let expression = Expression::from(coefficient);
+        transcendentals
+            .iter()
+            .enumerate()
+            .any(|(position, transcendental)| expression.contains(&transcendental.name(position)))
+    });
+    Err(match nested {
+        true => TranscendentalEquation,
+        false => SymbolicParameters,
+    })
+}
+
+// restricts the domain of the value to where the relation holds for the bound compared to zero
+fn restrict_domain(
+    value: &mut Term,
+    bound: &Term,
+    relation: RelationType,
+    settings: &Settings,
+) -> Result<(), EquationError> {
+    let domain = match inequality::solve(bound, &Term::new_multiplier(), relation, settings)? {
+        Intervals(intervals) => intervals,
+        _ => Vec::new(),
+    };
+    value.exceptions_in_domain.extend(
+        &Restricted {
+            exceptions: HashMap::new(),
+            domain,
+        },
+        &settings.tolerance,
+    );
+    Ok(())
+}
+
+// Equations which are polynomial in a single exponential are solved for the exponential first
+// and equations which are linear in logarithms are turned into a polynomial equation by raising
+// the base to both sides. The zeroes of the divisors and the bounds of the domain split the real
+// line into intervals on which the original relation is checked
+pub fn solve(
+    term: &Term,
+    denominator: &Term,
+    transcendentals: &[Transcendental],
+    relation: RelationType,
+    settings: &Settings,
+) -> Result<EquationResult, EquationError> {
+    let periodic = used(
+        &monomials(term, transcendentals, settings)?,
+        transcendentals.len(),
+    )
+    .into_iter()
+    .any(|position| transcendentals[position].is_periodic());
+    if periodic {
+        return trigonometry::solve(term, denominator, transcendentals, relation, settings);
+    }
+    let candidates = candidates(term, transcendentals, settings)?;
+    let identity = candidates.is_none();
+    let candidates = candidates.unwrap_or_default();
+
+    let mut critical_points = candidates.clone();
+    critical_points.append(&mut inequality::boundary_points(term, settings)?);
+    for divisor in term.symbolic_divisors.iter().chain([denominator]) {
+        critical_points.append(&mut candidates_of_divisor(
+            divisor,
+            transcendentals,
+            settings,
+        )?);
+    }
+    critical_points.sort_by(|value, other| value.to_f64().total_cmp(&other.to_f64()));
+    critical_points.dedup_by(|value, other| {
+        settings
+            .tolerance
+            .approximately_equal(value.to_f64(), other.to_f64())
+    });
+
+    let holds = |value: f64| {
+        parameters(transcendentals, value).is_some_and(|parameters| {
+            inequality::holds_at(
+                term,
+                denominator,
+                relation,
+                value,
+                &parameters,
+                &settings.tolerance,
+            )
+        })
+    };
+    let solution_set = interval::intersection(
+        &inequality::sample(
+            &critical_points,
+            relation,
+            identity,
+            holds,
+            &settings.tolerance,
+        ),
+        &term.exceptions_in_domain.domain(),
+        &settings.tolerance,
+    );
+    Ok(EquationResult::from_candidates(
+        solution_set,
+        candidates,
+        relation,
+        &settings.tolerance,
+    ))
+}
+
+fn candidates_of_divisor(
+    divisor: &Term,
+    transcendentals: &[Transcendental],
+    settings: &Settings,
+) -> Result<Vec<Scalar>, EquationError> {
+    match candidates(divisor, transcendentals, settings)? {
+        Some(candidates) => Ok(candidates),
+        None => Err(DivisionByZero),
+    }
+}
+
+// the coefficients of the products of powers of the parameters in the term, where zero
+// coefficients are left out
+pub fn monomials(
+    term: &Term,
+    transcendentals: &[Transcendental],
+    settings: &Settings,
+) -> Result<Vec<(Vec<i32>, Term)>, EquationError> {
+    let mut monomials = vec![(Vec::new(), term.clear_denominators())];
+    for (position, transcendental) in transcendentals.iter().enumerate() {
+        let name = transcendental.name(position);
+        monomials = monomials
+            .into_iter()
+            .flat_map(|(powers, coefficient)| {
+                coefficient
+                    .coefficients_of(&name)
+                    .into_iter()
+                    .map(move |(power, part)| {
+                        let mut powers = powers.clone();
+                        powers.push(power);
+                        (powers, part)
+                    })
+            })
+            .collect();
+    }
+    monomials.retain(|(_, coefficient)| coefficient.degree(&settings.tolerance).is_some());
+    match monomials
+        .iter()
+        .any(|(_, coefficient)| coefficient.is_symbolic())
+    {
+        true => Err(SymbolicParameters),
+        false => Ok(monomials),
+    }
+}
+
+// the positions of the parameters which occur in the monomials
+pub fn used(monomials: &[(Vec<i32>, Term)], count: usize) -> Vec<usize> {
+    (0..count)
+        .filter(|position| monomials.iter().any(|(powers, _)| powers[*position] != 0))
+        .collect()
+}
+
+// the zeroes of the polynomial equation the term is transformed into, which are all values of
+// the variable if the term vanishes identically
+fn candidates(
+    term: &Term,
+    transcendentals: &[Transcendental],
+    settings: &Settings,
+) -> Result<Option<Vec<Scalar>>, EquationError> {
+    let monomials = monomials(term, transcendentals, settings)?;
+    let used = used(&monomials, transcendentals.len());
+    if used.is_empty() {
+        let mut polynomial = Term::new();
+        for (_, coefficient) in monomials.iter() {
+            polynomial.add_term(coefficient);
+        }
+        return Ok(match polynomial.degree(&settings.tolerance) {
+            None => None,
+            Some(_) => Some(polynomial.real_zeroes(settings)?),
+        });
+    }
+
+    // the variable can only occur inside of the functions
+    let monomials = monomials
+        .into_iter()
+        .map(
+            |(powers, coefficient)| match coefficient.degree(&settings.tolerance) == Some(0) {
+                true => Ok((powers, coefficient.coefficient(0))),
+                false => Err(TranscendentalEquation),
+            },
+        )
+        .collect::<Result<Vec<(Vec<i32>, Scalar)>, EquationError>>()?;
+    let count = |kind: fn(&Transcendental) -> bool| {
+        used.iter()
+            .filter(|position| kind(&transcendentals[**position]))
+            .count()
+    };
+    let exponentials = count(|transcendental| matches!(transcendental, Exponential { .. }));
+    let logarithms = count(|transcendental| matches!(transcendental, Logarithm { .. }));
+    match (exponentials, logarithms) {
+        (exponentials, _) if exponentials == used.len() => {
+            exponential_candidates(&monomials, &used, transcendentals, settings)
+        }
+        (0, _) if used.len() == 1 => {
+            inverse_candidates(&monomials, used[0], transcendentals, settings)
+        }
+        (_, logarithms) if logarithms == used.len() => {
+            logarithm_candidates(&monomials, &used, transcendentals, settings)
+        }
+        _ => Err(TranscendentalEquation),
+    }
+}
+
+// Every exponential is a power of u = b^(f(x)/m) for the base b and the exponent f of the first
+// one, so the equation is polynomial in u and each positive zero of it gives f(x) = m·log_b(u)
+fn exponential_candidates(
+    monomials: &[(Vec<i32>, Scalar)],
+    used: &[usize],
+    transcendentals: &[Transcendental],
+    settings: &Settings,
+) -> Result<Option<Vec<Scalar>>, EquationError> {
+    let (base, exponent) = match &transcendentals[used[0]] {
+        Exponential { base, exponent } => (base, exponent),
+        _ => unreachable!("the transcendentals are exponentials"),
+    };
+    let mut ratios = HashMap::new();
+    for position in used {
+        let ratio = match &transcendentals[*position] {
+            Exponential {
+                base: other_base,
+                exponent: other_exponent,
+            } => exponent_ratio(other_base, other_exponent, base, exponent, settings),
+            _ => None,
+        };
+        ratios.insert(*position, ratio.ok_or(TranscendentalEquation)?);
+    }
+    let multiple = ratios
+        .values()
+        .fold(BigInt::one(), |multiple, ratio| multiple.lcm(ratio.denom()));
+
+    let mut polynomial = Term::new();
+    for (powers, coefficient) in monomials {
+        let mut power = BigRational::from_integer(BigInt::from(0));
+        for position in used {
+            power += &ratios[position] * BigInt::from(powers[*position]);
+        }
+        let power = (power * &multiple)
+            .to_integer()
+            .to_i32()
+            .ok_or(TranscendentalEquation)?;
+        *polynomial.addends.entry(power).or_insert_with(Scalar::zero) += coefficient;
+    }
+    if polynomial.degree(&settings.tolerance).is_none() {
+        return Ok(None);
+    }
+
+    let multiple = Scalar::Exact(BigRational::from_integer(multiple));
+    let mut candidates = Vec::new();
+    for value in polynomial.real_zeroes(settings)? {
+        if value.to_f64() <= 0.0 {
+            continue;
+        }
+        let mut equation = exponent.clone();
+        let target = &multiple * &logarithm_of(&value, base).ok_or(ComplexNumbers)?;
+        *equation.addends.entry(0).or_insert_with(Scalar::zero) -= &target;
+        if equation.degree(&settings.tolerance).unwrap_or_default() > 0 {
+            candidates.append(&mut equation.real_zeroes(settings)?);
+        }
+    }
+    Ok(Some(candidates))
+}
+
+// The sum of the logarithms with coefficients c_i of the arguments A_i and the constant c is zero
+// exactly where the product of the A_i raised to m·c_i equals b^(-m·c) on the domain, where m
+// makes the exponents integers
+fn logarithm_candidates(
+    monomials: &[(Vec<i32>, Scalar)],
+    used: &[usize],
+    transcendentals: &[Transcendental],
+    settings: &Settings,
+) -> Result<Option<Vec<Scalar>>, EquationError> {
+    let reference_base = match &transcendentals[used[0]] {
+        Logarithm { base, .. } => base,
+        _ => unreachable!("the transcendentals are logarithms"),
+    };
+    let mut constant = Scalar::zero();
+    let mut weights: Vec<(&Term, BigRational)> = Vec::new();
+    for (powers, coefficient) in monomials {
+        let position = match powers.iter().sum::<i32>() {
+            0 => {
+                constant += coefficient;
+                continue;
+            }
+            1 => powers.iter().position(|power| *power == 1),
+            _ => None,
+        };
+        let (base, argument) = match position.map(|position| &transcendentals[position]) {
+            Some(Logarithm { base, argument }) => (base, argument),
+            _ => return Err(TranscendentalEquation),
+        };
+        let scale = logarithm_of(base, reference_base).ok_or(ComplexNumbers)?;
+        let weight =
+            rational(&(coefficient / &scale), &settings.tolerance).ok_or(TranscendentalEquation)?;
+        weights.push((argument, weight));
+    }
+    let multiple = weights.iter().fold(BigInt::one(), |multiple, (_, weight)| {
+        multiple.lcm(weight.denom())
+    });
+
+    let power = -&(&constant * &Scalar::Exact(BigRational::from_integer(multiple.clone())));
+    let mut left = Term::new_multiplier();
+    let mut right = Term::new_multiplier();
+    right.multiply_constant(&reference_base.pow(&power).ok_or(ComplexNumbers)?);
+    for (argument, weight) in weights {
+        let exponent = (weight * &multiple).to_integer();
+        let mut factor = argument.clone();
+        factor.raise(&Scalar::Exact(BigRational::from_integer(exponent.abs())))?;
+        match exponent.is_negative() {
+            true => right.multiply_term(&factor),
+            false => left.multiply_term(&factor),
+        }
+    }
+    right.multiply_constant(&Scalar::from(-1));
+    left.add_term(&right);
+    Ok(match left.degree(&settings.tolerance) {
+        None => None,
+        Some(_) => Some(left.real_zeroes(settings)?),
+    })
+}
+
+// Each zero u of the polynomial in a logarithm or an inverse trigonometric function F⁻¹(A(x))
+// which lies in its range gives A(x) = F(u)
+fn inverse_candidates(
+    monomials: &[(Vec<i32>, Scalar)],
+    position: usize,
+    transcendentals: &[Transcendental],
+    settings: &Settings,
+) -> Result<Option<Vec<Scalar>>, EquationError> {
+    let mut polynomial = Term::new();
+    for (powers, coefficient) in monomials {
+        *polynomial
+            .addends
+            .entry(powers[position])
+            .or_insert_with(Scalar::zero) += coefficient;
+    }
+    if polynomial.degree(&settings.tolerance).is_none() {
+        return Ok(None);
+    }
+
+    let mut candidates = Vec::new();
+    for value in polynomial.real_zeroes(settings)? {
+        let (argument, target) = match &transcendentals[position] {
+            Logarithm { base, argument } => (argument, base.pow(&value).ok_or(ComplexNumbers)?),
+            Trigonometric {
+                function,
+                argument,
+                unit,
+            } => {
+                let angle = value.to_f64();
+                let quarter_turn = trigonometry::half_turn(*unit).to_f64() / 2.0;
+                let (direct, in_range) = match function {
+                    Arcsine => (Sine, angle.abs() <= quarter_turn),
+                    Arccosine => (Cosine, (0.0..=2.0 * quarter_turn).contains(&angle)),
+                    Arctangent => (Tangent, angle.abs() < quarter_turn),
+                    _ => return Err(TranscendentalEquation),
+                };
+                if !in_range {
+                    continue;
+                }
+                (
+                    argument,
+                    trigonometry::value_of(&direct, &value, *unit, &settings.tolerance)?,
+                )
+            }
+            Exponential { .. } => unreachable!("exponentials are solved separately"),
+        };
+        let mut equation = argument.clone();
+        *equation.addends.entry(0).or_insert_with(Scalar::zero) -= &target;
+        if equation.degree(&settings.tolerance).unwrap_or_default() > 0 {
+            candidates.append(&mut equation.real_zeroes(settings)?);
+        }
+    }
+    Ok(Some(candidates))
+}
+
+// the rational r with ln(b)·f = r·ln(c)·g for the exponentials b^f and c^g
+fn exponent_ratio(
+    base: &Scalar,
+    exponent: &Term,
+    reference_base: &Scalar,
+    reference_exponent: &Term,
+    settings: &Settings,
+) -> Option<BigRational> {
+    let scale = logarithm_of(base, reference_base)?;
+    let leading = reference_exponent.degree(&settings.tolerance)?;
+    let ratio =
+        &(&exponent.coefficient(leading) * &scale) / &reference_exponent.coefficient(leading);
+    let proportional = exponent
+        .addends
+        .keys()
+        .chain(reference_exponent.addends.keys())
+        .all(|power| {
+            let difference = &(&exponent.coefficient(*power) * &scale)
+                - &(&ratio * &reference_exponent.coefficient(*power));
+            settings
+                .tolerance
+                .is_negligible(difference.to_f64(), ratio.to_f64().abs())
+        });
+    proportional
+        .then(|| rational(&ratio, &settings.tolerance))
+        .flatten()
+}
+
+// the logarithm of a positive value, which is exact if the value is a rational power of the base
+fn logarithm_of(value: &Scalar, base: &Scalar) -> Option<Scalar> {
+    if value.to_f64() <= 0.0 {
+        return None;
+    }
+    let approximation = value.to_f64().ln() / base.to_f64().ln();
+    if let (Scalar::Exact(value), Scalar::Exact(base)) = (value, base) {
+        for denominator in 1..=MAX_DENOMINATOR {
+            let numerator = (approximation * denominator as f64).round();
+            if numerator.abs() > MAX_EXACT_EXPONENT as f64 {
+                continue;
+            }
+            if base.pow(numerator as i32) == value.pow(denominator) {
+                return Some(Scalar::Exact(BigRational::new(
+                    BigInt::from(numerator as i32),
+                    BigInt::from(denominator),
+                )));
+            }
+        }
+    }
+    Some(Scalar::Approximate(approximation))
+}
+
+fn rational(value: &Scalar, tolerance: &Tolerance) -> Option<BigRational> {
+    match value {
+        Scalar::Exact(value) => Some(value.clone()),
+        Scalar::Approximate(value) => approximate_fraction(*value, tolerance),
+        Scalar::Surd(_) | Scalar::Symbolic(_) => None,
+    }
+}
+
+// the values of the functions at the value, which do not exist outside of their domains
+pub fn parameters(transcendentals: &[Transcendental], value: f64) -> Option<HashMap<String, f64>> {
+    transcendentals
+        .iter()
+        .enumerate()
+        .map(|(position, transcendental)| {
+            Some((
+                transcendental.name(position),
+                transcendental.evaluate(value)?,
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,347 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::f64::consts::PI;
+
+use crate::equation_element::{
+    Function::{self, *},
+    RelationType::{self, *},
+};
+use crate::equation_error::EquationError::{self, *};
+use crate::equation_result::EquationResult::{self, *};
+use crate::inequality;
+use crate::locale::Locale;
+use crate::scalar::{approximate_fraction, Scalar};
+use crate::settings::{AngleUnit, Settings, Tolerance};
+use crate::surd::Surd;
+use crate::term::Term;
+use crate::transcendental::{self, Transcendental};
+
+// angles are recognized as exact in steps of a twelfth of a half turn, which covers all multiples
+// of 30° and 45°
+const STEPS_PER_HALF_TURN: i32 = 12;
+
+pub fn half_turn(unit: AngleUnit) -> Scalar {
+    match unit {
+        AngleUnit::Radians => Scalar::Approximate(PI),
+        AngleUnit::Degrees => Scalar::from(180),
+    }
+}
+
+fn to_radians(angle: f64, unit: AngleUnit) -> f64 {
+    match unit {
+        AngleUnit::Radians => angle,
+        AngleUnit::Degrees => angle.to_radians(),
+    }
+}
+
+// the angle in radians in the unit, which is exact in degrees if it is a simple fraction of a
+// half turn
+fn from_radians(angle: f64, unit: AngleUnit, tolerance: &Tolerance) -> Scalar {
+    match unit {
+        AngleUnit::Radians => Scalar::Approximate(angle),
+        AngleUnit::Degrees => match approximate_fraction(angle / PI, tolerance) {
+            Some(turns) => Scalar::Exact(turns * BigInt::from(180)),
+            None => Scalar::Approximate(angle.to_degrees()),
+        },
+    }
+}
+
+// the tangent is only undefined where the cosine is exactly zero, which no approximate angle is,
+// because the angle closest to a pole is the solution where the tangent is very large
+pub fn evaluate(function: &Function, argument: f64, unit: AngleUnit) -> Option<f64> {
+    let value = match function {
+        Sine => to_radians(argument, unit).sin(),
+        Cosine => to_radians(argument, unit).cos(),
+        Tangent => to_radians(argument, unit).tan(),
+        Arcsine => argument.asin() * to_radians(1.0, unit).recip(),
+        Arccosine => argument.acos() * to_radians(1.0, unit).recip(),
+        Arctangent => argument.atan() * to_radians(1.0, unit).recip(),
+        Logarithm(_) => unreachable!("logarithms are evaluated separately"),
+    };
+    value.is_finite().then_some(value)
+}
+
+// The value of the function at a constant, which is exact at multiples of 30° and 45° and for
+// inverse functions whose result is such an angle in degrees
+pub fn value_of(
+    function: &Function,
+    argument: &Scalar,
+    unit: AngleUnit,
+    tolerance: &Tolerance,
+) -> Result<Scalar, EquationError> {
+    if let Sine | Cosine | Tangent = function {
+        let steps = approximate_fraction((argument / &half_turn(unit)).to_f64(), tolerance)
+            .map(|turns| turns * BigInt::from(STEPS_PER_HALF_TURN))
+            .filter(|steps| steps.is_integer())
+            .and_then(|steps| steps.to_integer().to_i32());
+        if let Some(steps) = steps {
+            return match function {
+                Sine => Ok(special_sine(steps)),
+                Cosine => Ok(special_sine(steps + STEPS_PER_HALF_TURN / 2)),
+                _ => special_tangent(steps).ok_or(DivisionByZero),
+            };
+        }
+    }
+    let value = argument.to_f64();
+    if let Arcsine | Arccosine = function {
+        if value.abs() > 1.0 {
+            return Err(ComplexNumbers);
+        }
+    }
+    match function {
+        Sine | Cosine | Tangent => evaluate(function, value, unit)
+            .map(Scalar::Approximate)
+            .ok_or(DivisionByZero),
+        Arcsine => Ok(from_radians(value.asin(), unit, tolerance)),
+        Arccosine => Ok(from_radians(value.acos(), unit, tolerance)),
+        Arctangent => Ok(from_radians(value.atan(), unit, tolerance)),
+        Logarithm(_) => unreachable!("logarithms are evaluated separately"),
+    }
+}
+
+// the distance of a number of steps to the closest multiple of a half turn
+fn reduce(steps: i32) -> i32 {
+    let steps = steps.rem_euclid(STEPS_PER_HALF_TURN);
+    steps.min(STEPS_PER_HALF_TURN - steps)
+}
+
+// the square of the sine at a number of steps up to a quarter turn
+fn sine_square(steps: i32) -> Option<BigRational> {
+    let (numerator, denominator) = match steps {
+        0 => (0, 1),
+        2 => (1, 4),
+        3 => (1, 2),
+        4 => (3, 4),
+        6 => (1, 1),
+        _ => return None,
+    };
+    Some(BigRational::new(
+        BigInt::from(numerator),
+        BigInt::from(denominator),
+    ))
+}
+
+fn signed_root(square: &BigRational, negative: bool) -> Scalar {
+    if square.is_zero() {
+        return Scalar::zero();
+    }
+    let root = Surd::sqrt(square);
+    let root = match root.radicand.is_one() {
+        true => Scalar::Exact(root.coefficient),
+        false => Scalar::Surd(root),
+    };
+    match negative {
+        true => -&root,
+        false => root,
+    }
+}
+
+// the sine at a number of steps, which is only simple at multiples of 30° and 45°
+fn special_sine(steps: i32) -> Scalar {
+    let steps = steps.rem_euclid(2 * STEPS_PER_HALF_TURN);
+    match sine_square(reduce(steps)) {
+        Some(square) => signed_root(&square, steps > STEPS_PER_HALF_TURN),
+        None => Scalar::Approximate((steps as f64 * PI / STEPS_PER_HALF_TURN as f64).sin()),
+    }
+}
+
+fn special_tangent(steps: i32) -> Option<Scalar> {
+    let sine = special_sine(steps);
+    let cosine = special_sine(steps + STEPS_PER_HALF_TURN / 2);
+    if cosine.is_zero() {
+        return None;
+    }
+    Some(match sine_square(reduce(steps)) {
+        Some(square) => signed_root(
+            &(&square / &(BigRational::one() - &square)),
+            sine.is_negative() != cosine.is_negative(),
+        ),
+        None => &sine / &cosine,
+    })
+}
+
+// The solutions of an equation which is polynomial in a sine, cosine or tangent of a linear
+// argument kx + c form families which repeat with the period of the function divided by |k|. Each
+// real zero of the polynomial gives up to two angles per period, which are checked against the
+// original equation
+pub fn solve(
+    term: &Term,
+    denominator: &Term,
+    transcendentals: &[Transcendental],
+    relation: RelationType,
+    settings: &Settings,
+) -> Result<EquationResult, EquationError> {
+    if relation != Equality {
+        return Err(PeriodicInequality);
+    }
+    let monomials = transcendental::monomials(term, transcendentals, settings)?;
+    let used = transcendental::used(&monomials, transcendentals.len());
+    let (function, argument, unit) = match used[..] {
+        [position] => match &transcendentals[position] {
+            Transcendental::Trigonometric {
+                function,
+                argument,
+                unit,
+            } if argument.degree(&settings.tolerance) == Some(1)
+                && argument.lowest_exponent(&settings.tolerance) >= 0 =>
+            {
+                (function, argument, *unit)
+            }
+            _ => return Err(TranscendentalEquation),
+        },
+        _ => return Err(TranscendentalEquation),
+    };
+    let periodic = |term: &Term| {
+        transcendental::monomials(term, transcendentals, settings).is_ok_and(|monomials| {
+            monomials.iter().all(|(powers, coefficient)| {
+                coefficient.degree(&settings.tolerance) == Some(0)
+                    && powers
+                        .iter()
+                        .enumerate()
+                        .all(|(position, power)| *power == 0 || used.contains(&position))
+            })
+        })
+    };
+    if !term.exceptions_in_domain.is_empty()
+        || !term
+            .symbolic_divisors
+            .iter()
+            .chain([denominator])
+            .all(periodic)
+    {
+        return Err(TranscendentalEquation);
+    }
+
+    let mut polynomial = Term::new();
+    for (powers, coefficient) in &monomials {
+        if coefficient.degree(&settings.tolerance) != Some(0) {
+            return Err(TranscendentalEquation);
+        }
+        *polynomial
+            .addends
+            .entry(powers[used[0]])
+            .or_insert_with(Scalar::zero) += &coefficient.coefficient(0);
+    }
+
+    let half_turn = half_turn(unit);
+    let turn = &Scalar::from(2) * &half_turn;
+    let mut angles = Vec::new();
+    for value in polynomial.real_zeroes(settings)? {
+        let value = value.to_f64();
+        match function {
+            Sine if value.abs() <= 1.0 => {
+                let angle = from_radians(value.asin(), unit, &settings.tolerance);
+                angles.push(&half_turn - &angle);
+                angles.push(angle);
+            }
+            Cosine if value.abs() <= 1.0 => {
+                let angle = from_radians(value.acos(), unit, &settings.tolerance);
+                angles.push(-&angle);
+                angles.push(angle);
+            }
+            Tangent => angles.push(from_radians(value.atan(), unit, &settings.tolerance)),
+            _ => {}
+        }
+    }
+
+    let period = match function {
+        Tangent => half_turn,
+        _ => turn,
+    };
+    let slope = argument.coefficient(1);
+    let period = &period / &slope.abs();
+    let mut solutions = angles
+        .into_iter()
+        .map(|angle| {
+            normalize(
+                &(&(&angle - &argument.coefficient(0)) / &slope),
+                &period,
+                &settings.tolerance,
+            )
+        })
+        // the angles solve the polynomial in the function, whose roots were verified, so only the
+        // divisors are checked instead of comparing the equation with zero, which fails for large
+        // values of the function like the tangent close to its poles
+        .filter(|solution| {
+            transcendental::parameters(transcendentals, solution.to_f64()).is_some_and(
+                |parameters| {
+                    inequality::divisors_defined_at(
+                        term,
+                        denominator,
+                        solution.to_f64(),
+                        &parameters,
+                        &settings.tolerance,
+                    )
+                },
+            )
+        })
+        .collect::<Vec<Scalar>>();
+    solutions.sort_by(|value, other| value.to_f64().total_cmp(&other.to_f64()));
+    solutions.dedup_by(|value, other| {
+        settings
+            .tolerance
+            .is_negligible(value.to_f64() - other.to_f64(), period.to_f64())
+    });
+    Ok(match solutions.is_empty() {
+        true => Unsolvable,
+        false => PeriodicSolutions { solutions, period },
+    })
+}
+
+// the member of the family in [0, period)
+fn normalize(value: &Scalar, period: &Scalar, tolerance: &Tolerance) -> Scalar {
+    let turns = value.to_f64() / period.to_f64();
+    let mut whole_turns = turns.floor();
+    if tolerance.approximately_equal(turns - whole_turns, 1.0) {
+        whole_turns += 1.0;
+    }
+    let normalized = value - &(period * &Scalar::from(whole_turns as i32));
+    match tolerance.is_negligible(normalized.to_f64(), period.to_f64()) {
+        true => Scalar::zero(),
+        false => normalized,
+    }
+}
+
+// Angles which are simple fractions of π are written as such, e.g. 5π/6. Formatting does not
+// depend on the settings, so the fractions are recognized with the default tolerance
+pub fn format_angle(value: &Scalar, locale: Locale) -> String {
+    match value {
+        Scalar::Approximate(angle) => match approximate_fraction(angle / PI, &Tolerance::default())
+        {
+            Some(turns) => format_multiple_of_pi(&turns, ""),
+            None => value.format(locale),
+        },
+        _ => value.format(locale),
+    }
+}
+
+// the period written with the integer k, e.g. 2kπ or 360k
+pub fn format_period(period: &Scalar, locale: Locale) -> String {
+    match period {
+        Scalar::Approximate(angle) => match approximate_fraction(angle / PI, &Tolerance::default())
+        {
+            Some(turns) => format_multiple_of_pi(&turns, "k"),
+            None => format!("{}k", period.format(locale)),
+        },
+        _ => format!("{}k", period.format(locale)),
+    }
+}
+
+fn format_multiple_of_pi(turns: &BigRational, factor: &str) -> String {
+    if turns.is_zero() {
+        return String::from("0");
+    }
+    let numerator = match turns.numer().abs() == BigInt::from(1) {
+        true => format!("{factor}π"),
+        false => format!("{}{factor}π", turns.numer().abs()),
+    };
+    let sign = match turns.is_negative() {
+        true => "-",
+        false => "",
+    };
+    match turns.is_integer() {
+        true => format!("{sign}{numerator}"),
+        false => format!("{sign}{numerator}/{}", turns.denom()),
+    }
+}
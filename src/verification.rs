@@ -0,0 +1,609 @@
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::equation_element::{
+    AdditiveOperationType::{self, *},
+    EquationElement::{self, *},
+    Function::{self, *},
+    MultiplicativeOperationType::{self, *},
+    OperationType::*,
+    RelationType::*,
+    SymbolType::*,
+    Token,
+    ValueType::*,
+};
+use crate::equation_result::EquationResult::{self, *};
+use crate::locale::Locale;
+use crate::scalar::{approximate_fraction, Scalar};
+use crate::settings::{AngleUnit, Settings, Tolerance};
+use crate::trigonometry::{self, format_angle};
+
+// The sides of an equation as they were entered, before any fraction was cleared or any root was
+// raised to a power. They are evaluated as trees without expanding products and powers, so they
+// do not share the rounding errors or the mistakes of the terms the equation is solved with
+pub struct OriginalEquation {
+    // there are no sides if the tokens use a form the evaluator cannot read
+    sides: Option<(Node, Node)>,
+}
+
+#[derive(Clone)]
+enum Node {
+    Constant(f64),
+    Variable,
+    Negation(Box<Node>),
+    Sum(AdditiveOperationType, Box<Node>, Box<Node>),
+    Product(MultiplicativeOperationType, Box<Node>, Box<Node>),
+    Power(Box<Node>, Box<Node>),
+    AbsoluteValue(Box<Node>),
+    Function(Function, Box<Node>),
+}
+
+// why a candidate does not solve the original equation
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Rejection {
+    DivisionByZero,
+    // a root of a negative number, a logarithm of a non-positive number or an inverse sine or
+    // cosine outside of [-1, 1]
+    Undefined,
+    Residual(f64),
+}
+
+// the solutions with their residuals |lhs - rhs| in the original equation and the candidates
+// which were removed from the result
+#[derive(PartialEq, Debug, Default)]
+pub struct Verification {
+    pub solutions: Vec<Scalar>,
+    pub residuals: Vec<f64>,
+    pub rejected: Vec<Scalar>,
+    pub reasons: Vec<Rejection>,
+    // the solutions which are kept although the original equation could not be evaluated there
+    pub unverified: Vec<Scalar>,
+}
+
+impl OriginalEquation {
+    // equations with symbolic parameters or relations other than equality are not verified
+    pub fn parse(tokens: &[Token]) -> Option<Self> {
+        let symbolic = tokens.iter().any(|token| {
+            matches!(&token.element, Value(Number(value) | Symbol(Constant(value))) if value.is_symbolic())
+        });
+        let equality = tokens
+            .iter()
+            .filter_map(|token| match token.element {
+                Separator(relation) => Some(relation),
+                _ => None,
+            })
+            .eq([Equality]);
+        if symbolic || !equality {
+            return None;
+        }
+
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        Some(Self {
+            sides: parser.equation(),
+        })
+    }
+
+    // The difference lhs - rhs at the value together with the magnitude of the terms it was
+    // computed from. There is none if the sides cannot be read or overflow floating point
+    // numbers, such as 1e400/1e399
+    pub fn difference(
+        &self,
+        value: f64,
+        settings: &Settings,
+    ) -> Result<Option<(f64, f64)>, Rejection> {
+        let (left_hand_side, right_hand_side) = match &self.sides {
+            Some(sides) => sides,
+            None => return Ok(None),
+        };
+        let (left_value, left_magnitude) = left_hand_side.evaluate(value, settings)?;
+        let (right_value, right_magnitude) = right_hand_side.evaluate(value, settings)?;
+        let difference = left_value - right_value;
+        Ok(difference
+            .is_finite()
+            .then_some((difference, left_magnitude + right_magnitude)))
+    }
+
+    // The residual |lhs - rhs| at the value, which is too large if it exceeds the slope of the
+    // difference of the sides times the error of the value plus the rounding errors of the terms it
+    // was computed from. There is no residual if the sides cannot be evaluated
+    fn check(&self, value: f64, error: f64, settings: &Settings) -> Result<Option<f64>, Rejection> {
+        let tolerance = &settings.tolerance;
+        let (difference, magnitude) = match self.difference(value, settings)? {
+            Some(difference) => difference,
+            None => return Ok(None),
+        };
+
+        // every root is only known up to the precision, which a steep function turns into a large
+        // residual. The slope is taken towards the neighbouring floating point numbers, which stay
+        // on the side of a pole the value lies on
+        let error = error.max(settings.precision * value.abs());
+        let step = f64::EPSILON / 2.0 * value.abs().max(error);
+        let slope = [value - step, value + step]
+            .into_iter()
+            .filter_map(|neighbour| {
+                let (neighbour_difference, _) = self.difference(neighbour, settings).ok()??;
+                Some((neighbour_difference - difference).abs() / (neighbour - value).abs())
+            })
+            .filter(|slope| slope.is_finite())
+            .fold(0.0, f64::max);
+        let residual = difference.abs();
+        match residual <= slope * error + tolerance.relative * magnitude + tolerance.absolute {
+            true => Ok(Some(residual)),
+            false => Err(Rejection::Residual(residual)),
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+// The tokens are read with the precedence the equation was built with: products are evaluated
+// from left to right, a factor directly followed by another one is multiplied with it and
+// exponents bind to the factor before them
+impl Parser<'_> {
+    fn equation(&mut self) -> Option<(Node, Node)> {
+        let left_hand_side = self.sum()?;
+        self.close(Separator(Equality))?;
+        let right_hand_side = self.sum()?;
+        match self.next() {
+            None => Some((left_hand_side, right_hand_side)),
+            Some(_) => None,
+        }
+    }
+
+    fn peek(&self) -> Option<&EquationElement> {
+        self.tokens.get(self.position).map(|token| &token.element)
+    }
+
+    fn next(&mut self) -> Option<&EquationElement> {
+        self.position += 1;
+        self.tokens
+            .get(self.position - 1)
+            .map(|token| &token.element)
+    }
+
+    fn sum(&mut self) -> Option<Node> {
+        let mut node = match self.peek() {
+            Some(Operation(AdditiveOperation(Subtraction))) => {
+                self.next();
+                Node::Negation(Box::new(self.product()?))
+            }
+            Some(Operation(AdditiveOperation(Addition))) => {
+                self.next();
+                self.product()?
+            }
+            _ => self.product()?,
+        };
+        while let Some(Operation(AdditiveOperation(operation))) = self.peek() {
+            let operation = operation.clone();
+            self.next();
+            node = Node::Sum(operation, Box::new(node), Box::new(self.product()?));
+        }
+        Some(node)
+    }
+
+    fn product(&mut self) -> Option<Node> {
+        let mut node = self.power()?;
+        loop {
+            let operation = match self.peek() {
+                Some(Operation(MultiplicativeOperation(operation))) => {
+                    let operation = operation.clone();
+                    self.next();
+                    operation
+                }
+                Some(Value(_) | OpeningParenthesis | OpeningFunction(_) | OpeningAbsoluteValue) => {
+                    Multiplication
+                }
+                _ => return Some(node),
+            };
+            node = Node::Product(operation, Box::new(node), Box::new(self.power()?));
+        }
+    }
+
+    fn power(&mut self) -> Option<Node> {
+        let base = self.primary()?;
+        match self.peek() {
+            Some(Operation(Exponentiation)) => {
+                self.next();
+                Some(Node::Power(Box::new(base), Box::new(self.exponent()?)))
+            }
+            _ => Some(base),
+        }
+    }
+
+    // an optionally negated value or parenthesized expression, which can be raised to a power
+    // itself
+    fn exponent(&mut self) -> Option<Node> {
+        let negative = matches!(self.peek(), Some(Operation(AdditiveOperation(Subtraction))));
+        if negative {
+            self.next();
+        }
+        let exponent = self.power()?;
+        Some(match negative {
+            true => Node::Negation(Box::new(exponent)),
+            false => exponent,
+        })
+    }
+
+    fn primary(&mut self) -> Option<Node> {
+        let node = match self.next()?.clone() {
+            Value(Number(value) | Symbol(Constant(value))) if !value.is_symbolic() => {
+                Node::Constant(value.to_f64())
+            }
+            Value(Symbol(Variable)) => Node::Variable,
+            OpeningParenthesis => {
+                let node = self.sum()?;
+                self.close(ClosingParenthesis)?;
+                node
+            }
+            OpeningFunction(function) => {
+                let argument = self.sum()?;
+                self.close(ClosingParenthesis)?;
+                Node::Function(function, Box::new(argument))
+            }
+            OpeningAbsoluteValue => {
+                let argument = self.sum()?;
+                self.close(ClosingAbsoluteValue)?;
+                Node::AbsoluteValue(Box::new(argument))
+            }
+            _ => return None,
+        };
+        Some(node)
+    }
+
+    fn close(&mut self, element: EquationElement) -> Option<()> {
+        (self.next()? == &element).then_some(())
+    }
+}
+
+impl Node {
+    // The value at the variable together with the magnitude of the terms it was computed from,
+    // which bounds how much rounding errors in those terms can move the value
+    fn evaluate(&self, value: f64, settings: &Settings) -> Result<(f64, f64), Rejection> {
+        Ok(match self {
+            Node::Constant(constant) => (*constant, constant.abs()),
+            Node::Variable => (value, value.abs()),
+            Node::Negation(node) => {
+                let (value, magnitude) = node.evaluate(value, settings)?;
+                (-value, magnitude)
+            }
+            Node::AbsoluteValue(node) => {
+                let (value, magnitude) = node.evaluate(value, settings)?;
+                (value.abs(), magnitude)
+            }
+            Node::Sum(operation, left, right) => {
+                let (left, left_magnitude) = left.evaluate(value, settings)?;
+                let (right, right_magnitude) = right.evaluate(value, settings)?;
+                let sum = match operation {
+                    Addition => left + right,
+                    Subtraction => left - right,
+                };
+                (sum, left_magnitude + right_magnitude)
+            }
+            Node::Product(operation, left, right) => {
+                // a factor with a negative exponent switches between multiplication and division
+                // like it does when the equation is built, so 2/x^-1 is 2x
+                let reciprocal = right.reciprocal(settings);
+                let (operation, right) = match (&reciprocal, operation) {
+                    (Some(reciprocal), Multiplication) => (Division, reciprocal),
+                    (Some(reciprocal), Division) => (Multiplication, reciprocal),
+                    (None, operation) => (operation.clone(), &**right),
+                };
+                let (left, left_magnitude) = left.evaluate(value, settings)?;
+                let (right, right_magnitude) = right.evaluate(value, settings)?;
+                match operation {
+                    Multiplication => (left * right, left_magnitude * right_magnitude),
+                    Division => {
+                        if right.is_finite()
+                            && settings.tolerance.is_negligible(right, right_magnitude)
+                        {
+                            return Err(Rejection::DivisionByZero);
+                        }
+                        let quotient = left / right;
+                        (
+                            quotient,
+                            (left_magnitude + quotient.abs() * right_magnitude) / right.abs(),
+                        )
+                    }
+                }
+            }
+            Node::Power(base, exponent) => {
+                let (base, base_magnitude) = base.evaluate(value, settings)?;
+                let (exponent_value, exponent_magnitude) = exponent.evaluate(value, settings)?;
+                if exponent_value < 0.0 && settings.tolerance.is_negligible(base, base_magnitude) {
+                    return Err(Rejection::DivisionByZero);
+                }
+                let power =
+                    power(base, exponent_value, &settings.tolerance).ok_or(Rejection::Undefined)?;
+                let magnitude = match exponent_value > 0.0 && exponent.is_constant() {
+                    true => base_magnitude.powf(exponent_value),
+                    false => {
+                        power.abs()
+                            * (1.0
+                                + exponent_value.abs() * base_magnitude / base.abs()
+                                + base.abs().ln().abs() * exponent_magnitude)
+                    }
+                };
+                (power, magnitude)
+            }
+            Node::Function(function, argument) => {
+                let (argument, argument_magnitude) = argument.evaluate(value, settings)?;
+                let (value, slope) = evaluate_function(function, argument, settings.angle_unit)?;
+                (value, value.abs() + slope.abs() * argument_magnitude)
+            }
+        })
+    }
+
+    // the power with the negated exponent if the exponent is a negative constant
+    fn reciprocal(&self, settings: &Settings) -> Option<Node> {
+        match self {
+            Node::Power(base, exponent) if exponent.is_constant() => {
+                match exponent.evaluate(0.0, settings) {
+                    Ok((exponent_value, _)) if exponent_value < 0.0 => Some(Node::Power(
+                        base.clone(),
+                        Box::new(Node::Negation(exponent.clone())),
+                    )),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        match self {
+            Node::Constant(_) => true,
+            Node::Variable => false,
+            Node::Negation(node) | Node::AbsoluteValue(node) | Node::Function(_, node) => {
+                node.is_constant()
+            }
+            Node::Sum(_, left, right)
+            | Node::Product(_, left, right)
+            | Node::Power(left, right) => left.is_constant() && right.is_constant(),
+        }
+    }
+}
+
+// the real power, where negative numbers only have roots of odd index like the cube root
+fn power(base: f64, exponent: f64, tolerance: &Tolerance) -> Option<f64> {
+    if base >= 0.0 || exponent.fract() == 0.0 {
+        return Some(base.powf(exponent));
+    }
+    let exponent = approximate_fraction(exponent, tolerance)?;
+    if exponent.denom() % 2 == 0.into() {
+        return None;
+    }
+    let root = base.abs().powf(Scalar::Exact(exponent.clone()).to_f64());
+    Some(match exponent.numer() % 2 == 0.into() {
+        true => root,
+        false => -root,
+    })
+}
+
+// the value of the function and its derivative at the argument
+fn evaluate_function(
+    function: &Function,
+    argument: f64,
+    unit: AngleUnit,
+) -> Result<(f64, f64), Rejection> {
+    let radians_per_unit = match unit {
+        AngleUnit::Radians => 1.0,
+        AngleUnit::Degrees => PI / 180.0,
+    };
+    if let Logarithm(base) = function {
+        if argument <= 0.0 {
+            return Err(Rejection::Undefined);
+        }
+        let logarithm_of_base = base.to_f64().ln();
+        return Ok((
+            argument.ln() / logarithm_of_base,
+            1.0 / (argument * logarithm_of_base),
+        ));
+    }
+    let value = trigonometry::evaluate(function, argument, unit).ok_or(match function {
+        Tangent => Rejection::DivisionByZero,
+        _ => Rejection::Undefined,
+    })?;
+    let angle = argument * radians_per_unit;
+    let slope = match function {
+        Sine => angle.cos() * radians_per_unit,
+        Cosine => -angle.sin() * radians_per_unit,
+        Tangent => (1.0 + value * value) * radians_per_unit,
+        Arcsine | Arccosine => 1.0 / ((1.0 - argument * argument).sqrt() * radians_per_unit),
+        Arctangent => 1.0 / ((1.0 + argument * argument) * radians_per_unit),
+        Logarithm(_) => unreachable!("logarithms are evaluated above"),
+    };
+    Ok((value, slope))
+}
+
+// Substitutes the solutions of the result into the original equation. Candidates at which a side
+// is undefined or which leave a large residual are removed from the result
+pub fn verify(
+    result: EquationResult,
+    equation: &OriginalEquation,
+    settings: &Settings,
+) -> (EquationResult, Verification) {
+    let mut verification = Verification::default();
+    // the errors of the solutions, which are zero where the result does not estimate them
+    let mut check = |solutions: &[Scalar], errors: &[f64]| {
+        solutions
+            .iter()
+            .zip(errors)
+            .map(
+                |(solution, error)| match equation.check(solution.to_f64(), *error, settings) {
+                    Ok(Some(residual)) => {
+                        verification.solutions.push(solution.clone());
+                        verification.residuals.push(residual);
+                        true
+                    }
+                    Ok(None) => {
+                        verification.unverified.push(solution.clone());
+                        true
+                    }
+                    Err(reason) => {
+                        verification.rejected.push(solution.clone());
+                        verification.reasons.push(reason);
+                        false
+                    }
+                },
+            )
+            .collect::<Vec<bool>>()
+    };
+
+    let result = match result {
+        Solutions {
+            solutions,
+            multiplicities,
+        } => {
+            let kept = check(&solutions, &vec![0.0; solutions.len()]);
+            match kept.contains(&true) {
+                true => Solutions {
+                    solutions: retain(solutions, &kept),
+                    multiplicities: retain(multiplicities, &kept),
+                },
+                false => Unsolvable,
+            }
+        }
+        BoundedSolutions {
+            solutions,
+            multiplicities,
+            errors,
+        } => {
+            let kept = check(&solutions, &errors);
+            match kept.contains(&true) {
+                true => BoundedSolutions {
+                    solutions: retain(solutions, &kept),
+                    multiplicities: retain(multiplicities, &kept),
+                    errors: retain(errors, &kept),
+                },
+                false => Unsolvable,
+            }
+        }
+        FilteredSolutions {
+            solutions,
+            extraneous,
+        } => {
+            let kept = check(&solutions, &vec![0.0; solutions.len()]);
+            FilteredSolutions {
+                solutions: retain(solutions, &kept),
+                extraneous,
+            }
+        }
+        PeriodicSolutions { solutions, period } => {
+            let kept = check(&solutions, &vec![0.0; solutions.len()]);
+            match kept.contains(&true) {
+                true => PeriodicSolutions {
+                    solutions: retain(solutions, &kept),
+                    period,
+                },
+                false => Unsolvable,
+            }
+        }
+        NumericSolutions {
+            solutions,
+            tolerances,
+            range,
+        } => {
+            let kept = check(&solutions, &tolerances);
+            NumericSolutions {
+                solutions: retain(solutions, &kept),
+                tolerances: retain(tolerances, &kept),
+                range,
+            }
+        }
+        result @ (ComplexSolutions(_)
+        | Unsolvable
+        | InfiniteSolutions { .. }
+        | Intervals(_)
+        | ConditionalSolutions { .. }) => result,
+    };
+    (result, verification)
+}
+
+fn retain<T>(values: Vec<T>, kept: &[bool]) -> Vec<T> {
+    values
+        .into_iter()
+        .zip(kept)
+        .filter(|(_, kept)| **kept)
+        .map(|(value, _)| value)
+        .collect()
+}
+
+impl Verification {
+    // the residual of every solution in one line, e.g. |lhs - rhs| = 0 at x = 1
+    pub fn format_residuals(&self, variable_name: &str, locale: Locale) -> Option<String> {
+        if self.solutions.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "|lhs - rhs| = {}",
+            self.solutions
+                .iter()
+                .zip(&self.residuals)
+                .map(|(solution, residual)| format!(
+                    "{} at {variable_name} = {}",
+                    format_residual(*residual),
+                    format_angle(solution, locale)
+                ))
+                .collect::<Vec<String>>()
+                .join(locale.list_separator())
+        ))
+    }
+
+    // the solutions which could not be checked in one line, e.g. not verified at x = 10
+    pub fn format_unverified(&self, variable_name: &str, locale: Locale) -> Option<String> {
+        if self.unverified.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "not verified at {variable_name} = {}: the original equation could not be evaluated there",
+            locale.join(
+                &self
+                    .unverified
+                    .iter()
+                    .map(|solution| format_angle(solution, locale))
+                    .collect::<Vec<String>>()
+            )
+        ))
+    }
+
+    // a line for every removed candidate with the reason, e.g. removed x = 1: division by zero
+    pub fn format_rejections(&self, variable_name: &str, locale: Locale) -> Vec<String> {
+        self.rejected
+            .iter()
+            .zip(&self.reasons)
+            .map(|(candidate, reason)| {
+                format!(
+                    "removed {variable_name} = {}: {reason}",
+                    format_angle(candidate, locale)
+                )
+            })
+            .collect()
+    }
+}
+
+fn format_residual(residual: f64) -> String {
+    match residual == 0.0 {
+        true => String::from("0"),
+        false => format!("{residual:.0e}"),
+    }
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Rejection::DivisionByZero => write!(f, "division by zero in the original equation"),
+            Rejection::Undefined => write!(f, "the original equation is undefined there"),
+            Rejection::Residual(residual) => write!(
+                f,
+                "the residual {} in the original equation is too large",
+                format_residual(*residual)
+            ),
+        }
+    }
+}